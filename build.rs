@@ -10,6 +10,10 @@
 //!   macOS CI job which only checks that bindgen + compile succeed).
 //! - `BINDGEN_EXTRA_CLANG_ARGS` — forwarded to bindgen as extra `clang`
 //!   args (standard bindgen knob, repeated here for visibility).
+//! - `CPDB_BACKEND_INFO_DIR` — where cpdb-libs looks for backend service
+//!   files. Defaults to `<datadir>/print-backends` from `cpdb.pc`, then to
+//!   `/usr/share/print-backends` (cpdb-libs' own default) when pkg-config
+//!   cannot tell.
 //! - `DOCS_RS=1` (set automatically by docs.rs) — bypasses bindgen
 //!   entirely and writes a hand-rolled stub `cpdb_sys.rs`. docs.rs
 //!   builds in a sandbox without cpdb-libs installed, so the stub
@@ -27,6 +31,12 @@ fn main() {
     println!("cargo:rerun-if-env-changed=BINDGEN_EXTRA_CLANG_ARGS");
     println!("cargo:rerun-if-env-changed=PKG_CONFIG_PATH");
     println!("cargo:rerun-if-env-changed=DOCS_RS");
+    println!("cargo:rerun-if-env-changed=CPDB_BACKEND_INFO_DIR");
+
+    println!(
+        "cargo:rustc-env=CPDB_BACKEND_INFO_DIR={}",
+        backend_info_dir().display()
+    );
 
     // docs.rs builder doesn't have cpdb-libs available. Emit a stub
     // bindings file and skip all link directives.
@@ -80,6 +90,31 @@ fn main() {
         .expect("failed to write bindings file");
 }
 
+/// Resolves the directory cpdb-libs was configured to read backend
+/// service files from (its `CPDB_BACKEND_INFO_DIR` define, which is
+/// `$datadir/print-backends`).
+///
+/// An explicit `CPDB_BACKEND_INFO_DIR` env var wins; otherwise the
+/// `datadir` or `prefix` variable of `cpdb.pc` is used, falling back to
+/// `/usr/share/print-backends` when pkg-config cannot find cpdb-libs.
+fn backend_info_dir() -> PathBuf {
+    if let Some(dir) = env::var_os("CPDB_BACKEND_INFO_DIR") {
+        return PathBuf::from(dir);
+    }
+    let datadir = pkg_config::get_variable("cpdb", "datadir")
+        .ok()
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            pkg_config::get_variable("cpdb", "prefix")
+                .ok()
+                .filter(|prefix| !prefix.is_empty())
+                .map(|prefix| PathBuf::from(prefix).join("share"))
+        })
+        .unwrap_or_else(|| PathBuf::from("/usr/share"));
+    datadir.join("print-backends")
+}
+
 /// Writes a hand-rolled `cpdb_sys.rs` for docs.rs builds.
 ///
 /// The stub mirrors every symbol the crate references — types, struct
@@ -391,6 +426,7 @@ unsafe extern "C" {
         name: *const libc::c_char,
     ) -> gboolean;
     pub fn cpdbSaveSettingsToDisk(settings: *mut cpdb_settings_t);
+    pub fn cpdbSerializeToGVariant(settings: *mut cpdb_settings_t) -> *mut libc::c_void;
     pub fn cpdbReadSettingsFromDisk() -> *mut cpdb_settings_t;
 
     pub fn cpdbGetNewOptions() -> *mut cpdb_options_t;
@@ -12,6 +12,7 @@
 use cpdb_rs::{Frontend, Printer, init, version};
 use std::env;
 use std::fs;
+use std::time::Duration;
 
 type ExResult = Result<(), Box<dyn std::error::Error>>;
 
@@ -89,7 +90,10 @@ fn connect() -> Result<Frontend, Box<dyn std::error::Error>> {
 
 fn list_printers() -> ExResult {
     let frontend = connect()?;
-    let printers = frontend.get_printers()?;
+    let (printers, warnings) = frontend.discover_printers_with_warnings(Duration::from_secs(3))?;
+    for warning in &warnings {
+        eprintln!("warning: {warning}");
+    }
     if printers.is_empty() {
         println!("no printers discovered");
         return Ok(());
@@ -115,7 +119,7 @@ fn list_printers() -> ExResult {
 
 fn show_printer_info(name: &str) -> ExResult {
     let frontend = connect()?;
-    let p = frontend.get_printer(name)?;
+    let p = frontend.get_printer_by_name(name)?;
     println!("Name: {}", p.name().unwrap_or_default());
     println!("ID: {}", p.id().unwrap_or_default());
     println!("Location: {}", p.location().unwrap_or_default());
@@ -133,7 +137,7 @@ fn print_file(name: &str, file_path: &str) -> ExResult {
         return Ok(());
     }
     let frontend = connect()?;
-    let printer = frontend.get_printer(name)?;
+    let printer = frontend.get_printer_by_name(name)?;
     if !printer.is_accepting_jobs().unwrap_or(false) {
         eprintln!("printer is not accepting jobs");
         return Ok(());
@@ -147,28 +151,23 @@ fn print_file(name: &str, file_path: &str) -> ExResult {
 
 fn show_printer_options(name: &str) -> ExResult {
     let frontend = connect()?;
-    let printer = frontend.get_printer(name)?;
-    let common = [
-        "copies",
-        "page-ranges",
-        "orientation-requested",
-        "print-quality",
-        "sides",
-        "media",
-        "printer-resolution",
-    ];
-    for opt in common {
-        match printer.get_option(opt)? {
-            Some(v) => println!("  {opt}: {v}"),
-            None => println!("  {opt}: (unset)"),
+    let printer = frontend.get_printer_by_name(name)?;
+    // Ensure the backend has populated the options table before reading it.
+    printer.acquire_details();
+    match printer.format_options() {
+        Ok(lines) => {
+            for line in lines.lines() {
+                println!("  {line}");
+            }
         }
+        Err(e) => eprintln!("options unavailable: {e}"),
     }
     Ok(())
 }
 
 fn show_printer_media(name: &str) -> ExResult {
     let frontend = connect()?;
-    let printer = frontend.get_printer(name)?;
+    let printer = frontend.get_printer_by_name(name)?;
     let media = printer
         .get_current("media")
         .ok()
@@ -200,7 +199,7 @@ fn show_printer_media(name: &str) -> ExResult {
 
 fn save_printer_config(name: &str, config_file: &str) -> ExResult {
     let frontend = connect()?;
-    let printer = frontend.get_printer(name)?;
+    let printer = frontend.get_printer_by_name(name)?;
     match printer.pickle_to_file(config_file, &frontend) {
         Ok(()) => println!("saved {name} -> {config_file}"),
         Err(e) => eprintln!("save failed: {e}"),
@@ -80,21 +80,23 @@ fn run_command_loop(frontend: &Frontend) {
             "get-all-printers" => {
                 frontend.refresh_printers();
             }
-            "get-default-printer" => match frontend.get_default_printer() {
-                Ok(p) => println!(
+            "get-default-printer" => match frontend.default_printer() {
+                Ok(Some(p)) => println!(
                     "Default printer: {}#{}",
                     p.name().unwrap_or_default(),
                     p.backend_name().unwrap_or_default()
                 ),
-                Err(_) => println!("No default printer found"),
+                Ok(None) => println!("No default printer found"),
+                Err(e) => eprintln!("{}", e),
             },
             "get-default-printer-for-backend" => {
                 if parts.len() < 2 {
                     eprintln!("Usage: get-default-printer-for-backend <backend name>");
                 } else {
-                    match frontend.get_default_printer_for_backend(parts[1]) {
-                        Ok(p) => println!("{}", p.name().unwrap_or_default()),
-                        Err(_) => println!("No default printer for backend found"),
+                    match frontend.default_printer_for_backend(parts[1]) {
+                        Ok(Some(p)) => println!("{}", p.name().unwrap_or_default()),
+                        Ok(None) => println!("No default printer for backend found"),
+                        Err(e) => eprintln!("{}", e),
                     }
                 }
             }
@@ -103,9 +105,8 @@ fn run_command_loop(frontend: &Frontend) {
                     eprintln!("Usage: set-user-default-printer <printer id> <backend name>");
                 } else {
                     match frontend.find_printer(parts[1], parts[2]) {
-                        Ok(p) => match p.set_user_default() {
-                            Ok(true) => println!("Set printer as user default"),
-                            Ok(false) => println!("Couldn't set printer as user default"),
+                        Ok(p) => match frontend.set_user_default(&p) {
+                            Ok(()) => println!("Set printer as user default"),
                             Err(e) => eprintln!("{}", e),
                         },
                         Err(e) => eprintln!("{}", e),
@@ -117,9 +118,8 @@ fn run_command_loop(frontend: &Frontend) {
                     eprintln!("Usage: set-system-default-printer <printer id> <backend name>");
                 } else {
                     match frontend.find_printer(parts[1], parts[2]) {
-                        Ok(p) => match p.set_system_default() {
-                            Ok(true) => println!("Set printer as system default"),
-                            Ok(false) => println!("Couldn't set printer as system default"),
+                        Ok(p) => match frontend.set_system_default(&p) {
+                            Ok(()) => println!("Set printer as system default"),
                             Err(e) => eprintln!("{}", e),
                         },
                         Err(e) => eprintln!("{}", e),
@@ -419,7 +419,7 @@ fn run_command_loop(frontend: &Frontend) {
 
 // ─── Safe command implementations ────────────────────────────────────────────
 
-/// Lists all printer options using the safe `OptionsCollection` type.
+/// Lists all printer options, sorted by name.
 ///
 /// This function contains zero `unsafe` blocks.
 fn cmd_get_all_options(frontend: &Frontend, printer_id: &str, backend_name: &str) {
@@ -434,13 +434,13 @@ fn cmd_get_all_options(frontend: &Frontend, printer_id: &str, backend_name: &str
     // Ensure the backend has populated the options table before reading it.
     p.acquire_details();
 
-    match p.get_options_collection() {
-        Ok(collection) if collection.is_empty() => {
+    match p.get_all_options() {
+        Ok(options) if options.is_empty() => {
             println!("No options available.");
         }
-        Ok(collection) => {
-            println!("Retrieved {} options.", collection.len());
-            for opt in collection.iter() {
+        Ok(options) => {
+            println!("Retrieved {} options.", options.len());
+            for opt in &options {
                 println!("[+] {}", opt.name);
                 println!(" --> GROUP: {}", opt.group);
                 for val in &opt.supported_values {
@@ -449,38 +449,21 @@ fn cmd_get_all_options(frontend: &Frontend, printer_id: &str, backend_name: &str
                 println!(" --> DEFAULT: {}\n", opt.default_value);
             }
         }
-        Err(e) => eprintln!("get_options_collection failed: {}", e),
+        Err(e) => eprintln!("get_all_options failed: {}", e),
     }
 }
 
-// ─── Unsafe command implementations ──────────────────────────────────────────
-// TODO: cmd_get_all_media still iterates GHashTable directly. Will be
-// replaced with a safe MediaCollection type in a future PR.
-
+/// Lists every media size with its dimensions and margins.
 fn cmd_get_all_media(frontend: &Frontend, printer_id: &str, backend_name: &str) {
     match frontend.find_printer(printer_id, backend_name) {
-        Ok(p) => unsafe {
-            let opts = cpdb_rs::ffi::cpdbGetAllOptions(p.as_raw());
-            if opts.is_null() {
-                println!("No options.");
-                return;
-            }
-            println!("Retrieved {} medias.", (*opts).media_count);
-            let media_table = (*opts).media as *mut glib_sys::GHashTable;
-            if !media_table.is_null() {
-                let mut iter: glib_sys::GHashTableIter = std::mem::zeroed();
-                let mut _key: glib_sys::gpointer = std::ptr::null_mut();
-                let mut value: glib_sys::gpointer = std::ptr::null_mut();
-                glib_sys::g_hash_table_iter_init(&mut iter, media_table);
-                while glib_sys::g_hash_table_iter_next(&mut iter, &mut _key, &mut value)
-                    != glib_sys::GFALSE
-                {
-                    let media = value as *const cpdb_rs::ffi::cpdb_media_t;
-                    if !media.is_null() {
-                        print_media(media);
-                    }
+        Ok(p) => match p.list_media() {
+            Ok(media) => {
+                println!("Retrieved {} medias.", media.len());
+                for m in &media {
+                    print_media(m);
                 }
             }
+            Err(e) => eprintln!("list_media failed: {}", e),
         },
         Err(e) => eprintln!("{}", e),
     }
@@ -507,20 +490,16 @@ fn cmd_get_media_margins(
 
 // ─── Display helpers ─────────────────────────────────────────────────────────
 
-fn print_media(media: *const cpdb_rs::ffi::cpdb_media_t) {
-    unsafe {
-        let name = cstr_or((*media).name, "?");
-        println!("[+] Media: {}", name);
-        println!("   * width = {}", (*media).width);
-        println!("   * length = {}", (*media).length);
-        println!(" --> Supported margins: {}", (*media).num_margins);
-        println!("     left, right, top, bottom");
-        for i in 0..(*media).num_margins {
-            let m = &*(*media).margins.offset(i as isize);
-            println!("     * {}, {}, {}, {}", m.left, m.right, m.top, m.bottom);
-        }
-        println!();
+fn print_media(media: &cpdb_rs::MediaInfo) {
+    println!("[+] Media: {}", media.name);
+    println!("   * width = {}", media.width);
+    println!("   * length = {}", media.length);
+    println!(" --> Supported margins: {}", media.margins.len());
+    println!("     left, right, top, bottom");
+    for m in &media.margins {
+        println!("     * {}, {}, {}, {}", m.left, m.right, m.top, m.bottom);
     }
+    println!();
 }
 
 fn print_translations(map: &cpdb_rs::TranslationMap) {
@@ -535,14 +514,6 @@ fn print_translations(map: &cpdb_rs::TranslationMap) {
 
 // ─── Helpers ─────────────────────────────────────────────────────────────────
 
-fn cstr_or(ptr: *const libc::c_char, fallback: &str) -> String {
-    if ptr.is_null() {
-        fallback.to_string()
-    } else {
-        unsafe { std::ffi::CStr::from_ptr(ptr).to_string_lossy().into_owned() }
-    }
-}
-
 fn get_locale() -> String {
     std::env::var("LC_ALL")
         .or_else(|_| std::env::var("LC_MESSAGES"))
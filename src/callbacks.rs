@@ -13,6 +13,15 @@
 //!
 //! Both trampolines wrap the user closure in `catch_unwind` so a Rust
 //! panic does not unwind across the FFI boundary (which is UB).
+//!
+//! # Registration policy
+//!
+//! Each frontend has one *primary* observer slot and any number of
+//! *listeners*. [`crate::Frontend::set_observer`] (and the constructors
+//! that take an observer) replace the primary observer; the old closure
+//! is dropped immediately and a warning is logged. Use
+//! [`crate::Frontend::add_printer_listener`] when several independent
+//! consumers need the same events.
 
 use crate::ffi;
 use crate::printer::Printer;
@@ -45,14 +54,62 @@ impl PrinterUpdate {
     }
 }
 
+// ─── PrinterEvent enum ───────────────────────────────────────────────────────
+
+/// A printer callback together with its payload, as delivered to
+/// [`crate::Frontend::on_printer_event`].
+#[derive(Debug)]
+pub enum PrinterEvent<'a> {
+    /// A new printer was discovered.
+    Added(Printer<'a>),
+    /// The printer with this id was removed. cpdb-libs frees the printer
+    /// object right after the callback, so only its id is passed on.
+    Removed(String),
+    /// An existing printer's state field changed.
+    StateChanged(Printer<'a>),
+}
+
+impl<'a> PrinterEvent<'a> {
+    /// Builds the event for one observer callback.
+    pub(crate) fn new(printer: &'a Printer<'_>, update: PrinterUpdate) -> Self {
+        match update {
+            PrinterUpdate::Added => Self::Added(printer.view()),
+            PrinterUpdate::Removed => Self::Removed(printer.id().unwrap_or_default()),
+            PrinterUpdate::StateChanged => Self::StateChanged(printer.view()),
+        }
+    }
+
+    /// The kind of change, without the payload.
+    pub fn update(&self) -> PrinterUpdate {
+        match self {
+            Self::Added(_) => PrinterUpdate::Added,
+            Self::Removed(_) => PrinterUpdate::Removed,
+            Self::StateChanged(_) => PrinterUpdate::StateChanged,
+        }
+    }
+}
+
 // ─── Printer observer (no user_data) ─────────────────────────────────────────
 
 /// A boxed FnMut closure invoked for every printer-update event.
-type PrinterObserver = dyn FnMut(&Printer<'_>, PrinterUpdate) + Send;
+pub(crate) type PrinterObserver = dyn FnMut(&Printer<'_>, PrinterUpdate) + Send;
+
+/// Everything registered for one frontend.
+///
+/// Registration policy: the `primary` observer is a single slot —
+/// registering again replaces (and drops) the previous closure, with a
+/// warning logged so accidental re-registration is visible during
+/// development. `listeners` accumulate; each one stays registered until
+/// the frontend is dropped.
+#[derive(Default)]
+struct ObserverEntry {
+    primary: Option<Box<PrinterObserver>>,
+    listeners: Vec<Box<PrinterObserver>>,
+}
 
 /// One slot in the registry — wrapped in an Arc so the trampoline can
-/// release the global lock before invoking the user's closure.
-type ObserverSlot = Arc<Mutex<Box<PrinterObserver>>>;
+/// release the global lock before invoking the user's closures.
+type ObserverSlot = Arc<Mutex<ObserverEntry>>;
 
 /// The frontend-pointer-keyed observer registry. Globals are unavoidable
 /// here because `cpdb_printer_callback` carries no `user_data`.
@@ -67,19 +124,79 @@ fn lock_registry() -> std::sync::MutexGuard<'static, HashMap<usize, ObserverSlot
     registry().lock().unwrap_or_else(|p| p.into_inner())
 }
 
-/// Inserts `observer` into the registry keyed by `frontend`.
+/// Returns the slot for `frontend`, creating an empty one if needed.
+fn slot_for(frontend: *mut ffi::cpdb_frontend_obj_t) -> ObserverSlot {
+    Arc::clone(lock_registry().entry(frontend as usize).or_default())
+}
+
+/// Installs `observer` as the primary observer for `frontend`.
 ///
-/// Replaces any previously registered observer for that frontend.
+/// Replaces any previously registered primary observer, which is dropped
+/// before this returns. Returns `true` when a previous observer was
+/// replaced.
 pub(crate) fn register_printer_observer(
     frontend: *mut ffi::cpdb_frontend_obj_t,
     observer: Box<PrinterObserver>,
+) -> bool {
+    let slot = slot_for(frontend);
+    let previous = slot
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .primary
+        .replace(observer);
+    let replaced = previous.is_some();
+    if replaced {
+        log::warn!(
+            "printer observer for frontend {frontend:p} replaced; the previous closure is dropped"
+        );
+    }
+    // Dropped here, outside the slot lock, so a closure's destructor may
+    // not deadlock against a concurrent dispatch.
+    drop(previous);
+    replaced
+}
+
+/// Appends `listener` to the listeners for `frontend`.
+pub(crate) fn add_printer_listener(
+    frontend: *mut ffi::cpdb_frontend_obj_t,
+    listener: Box<PrinterObserver>,
 ) {
-    lock_registry().insert(frontend as usize, Arc::new(Mutex::new(observer)));
+    slot_for(frontend)
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .listeners
+        .push(listener);
 }
 
-/// Removes the observer for `frontend`, if any. Idempotent.
+/// Removes every observer and listener for `frontend`, if any. Idempotent.
 pub(crate) fn unregister_printer_observer(frontend: *mut ffi::cpdb_frontend_obj_t) {
-    lock_registry().remove(&(frontend as usize));
+    let removed = lock_registry().remove(&(frontend as usize));
+    drop(removed);
+}
+
+/// Invokes the primary observer and then every listener registered under
+/// `key`, absorbing panics.
+fn dispatch(key: usize, printer: &Printer<'_>, update: PrinterUpdate) {
+    let slot = {
+        let map = lock_registry();
+        match map.get(&key) {
+            Some(slot) => Arc::clone(slot),
+            None => return,
+        }
+    };
+
+    let _ = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        // Poisoning is benign here — the closures will get a fresh frame on
+        // the next event; previous panic state cannot affect this call.
+        let mut entry = slot.lock().unwrap_or_else(|p| p.into_inner());
+        let entry = &mut *entry;
+        if let Some(primary) = entry.primary.as_mut() {
+            primary(printer, update);
+        }
+        for listener in entry.listeners.iter_mut() {
+            listener(printer, update);
+        }
+    }));
 }
 
 /// `extern "C"` trampoline plugged into `cpdbGetNewFrontendObj`.
@@ -93,26 +210,17 @@ pub(crate) unsafe extern "C" fn printer_trampoline(
     printer: *mut ffi::cpdb_printer_obj_t,
     update: ffi::cpdb_printer_update_t,
 ) {
-    let slot = {
-        let map = lock_registry();
-        match map.get(&(frontend as usize)) {
-            Some(slot) => Arc::clone(slot),
-            None => return,
-        }
-    };
     let Some(update) = PrinterUpdate::from_raw(update) else {
         return;
     };
-    let Ok(printer) = Printer::from_raw_borrowed(printer) else {
+    let Ok(printer) = Printer::from_frontend_table(printer, frontend) else {
         return;
     };
-
-    let _ = std::panic::catch_unwind(AssertUnwindSafe(|| {
-        // Poisoning is benign here — the closure will get a fresh frame on
-        // the next event; previous panic state cannot affect this call.
-        let mut closure = slot.lock().unwrap_or_else(|p| p.into_inner());
-        closure(&printer, update);
-    }));
+    if update == PrinterUpdate::Added {
+        // cpdb-libs has just copied the saved settings into the new object.
+        let _ = printer.reapply_runtime_settings();
+    }
+    dispatch(frontend as usize, &printer, update);
 }
 
 // ─── Async completion (with user_data) ───────────────────────────────────────
@@ -156,3 +264,130 @@ pub(crate) unsafe extern "C" fn acquire_trampoline(
         closure(&printer, status != 0);
     }));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::printer::tests::zeroed_printer;
+    use std::ptr::NonNull;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    /// Sets its flag when dropped, i.e. when the owning closure is freed.
+    struct DropFlag(Arc<AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    /// A registry key that is never dereferenced. Using a live stack
+    /// address keeps keys distinct between tests running in parallel.
+    fn key(token: &u8) -> *mut ffi::cpdb_frontend_obj_t {
+        token as *const u8 as *mut ffi::cpdb_frontend_obj_t
+    }
+
+    #[test]
+    fn re_registering_drops_previous_observer() {
+        let token = 0u8;
+        let frontend = key(&token);
+        let first_dropped = Arc::new(AtomicBool::new(false));
+        let flag = DropFlag(Arc::clone(&first_dropped));
+
+        let replaced = register_printer_observer(
+            frontend,
+            Box::new(move |_, _| {
+                let _ = &flag;
+            }),
+        );
+        assert!(!replaced);
+        assert!(!first_dropped.load(Ordering::SeqCst));
+
+        let replaced = register_printer_observer(frontend, Box::new(|_, _| {}));
+        assert!(replaced);
+        assert!(
+            first_dropped.load(Ordering::SeqCst),
+            "the replaced closure must be dropped, not leaked"
+        );
+
+        unregister_printer_observer(frontend);
+    }
+
+    #[test]
+    fn unregister_drops_observer_and_listeners() {
+        let token = 0u8;
+        let frontend = key(&token);
+        let observer_dropped = Arc::new(AtomicBool::new(false));
+        let listener_dropped = Arc::new(AtomicBool::new(false));
+        let observer_flag = DropFlag(Arc::clone(&observer_dropped));
+        let listener_flag = DropFlag(Arc::clone(&listener_dropped));
+
+        register_printer_observer(
+            frontend,
+            Box::new(move |_, _| {
+                let _ = &observer_flag;
+            }),
+        );
+        add_printer_listener(
+            frontend,
+            Box::new(move |_, _| {
+                let _ = &listener_flag;
+            }),
+        );
+        unregister_printer_observer(frontend);
+
+        assert!(observer_dropped.load(Ordering::SeqCst));
+        assert!(listener_dropped.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn dispatch_reaches_primary_and_every_listener() {
+        let token = 0u8;
+        let frontend = key(&token);
+        let calls = Arc::new(AtomicUsize::new(0));
+        for _ in 0..2 {
+            let calls = Arc::clone(&calls);
+            add_printer_listener(
+                frontend,
+                Box::new(move |_, _| {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                }),
+            );
+        }
+        let primary_calls = Arc::clone(&calls);
+        register_printer_observer(
+            frontend,
+            Box::new(move |_, update| {
+                assert_eq!(update, PrinterUpdate::Added);
+                primary_calls.fetch_add(1, Ordering::SeqCst);
+            }),
+        );
+
+        // The closures never touch the printer, so a dangling handle is fine.
+        let printer = Printer::from_raw_borrowed(NonNull::dangling().as_ptr()).unwrap();
+        dispatch(frontend as usize, &printer, PrinterUpdate::Added);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        unregister_printer_observer(frontend);
+        dispatch(frontend as usize, &printer, PrinterUpdate::Added);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn printer_event_carries_the_matching_payload() {
+        // Every string field is null, which `id()` reads as an empty string.
+        let mut obj = zeroed_printer();
+        let printer = Printer::from_raw_borrowed(&mut obj).unwrap();
+
+        let event = PrinterEvent::new(&printer, PrinterUpdate::Added);
+        assert!(matches!(&event, PrinterEvent::Added(p) if p.as_raw() == printer.as_raw()));
+        assert_eq!(event.update(), PrinterUpdate::Added);
+
+        let event = PrinterEvent::new(&printer, PrinterUpdate::StateChanged);
+        assert!(matches!(event, PrinterEvent::StateChanged(_)));
+
+        let event = PrinterEvent::new(&printer, PrinterUpdate::Removed);
+        assert!(matches!(&event, PrinterEvent::Removed(id) if id.is_empty()));
+        assert_eq!(event.update(), PrinterUpdate::Removed);
+    }
+}
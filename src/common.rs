@@ -5,6 +5,8 @@ use crate::error::{CpdbError, Result};
 use crate::ffi;
 use crate::util;
 use std::ffi::CString;
+use std::fmt;
+use std::path::Path;
 
 /// Returns the version of the linked cpdb-libs C library.
 pub fn version() -> Result<String> {
@@ -16,6 +18,103 @@ pub fn version() -> Result<String> {
     unsafe { util::cstr_to_string(raw) }
 }
 
+/// A cpdb-libs version split into numeric components.
+///
+/// Produced by [`parsed_version`] and [`SemVer::parse`]. Besides strict
+/// `MAJOR.MINOR.PATCH`, the release formats cpdb-libs has used are
+/// accepted: a leading `v`, a missing patch (`2.0`), and pre-release tags
+/// either hyphenated (`2.0.0-rc1`) or attached (`2.0b4`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SemVer {
+    /// Major version.
+    pub major: u32,
+    /// Minor version.
+    pub minor: u32,
+    /// Patch version; 0 when the string had none.
+    pub patch: u32,
+    /// Pre-release tag such as `"b4"` or `"rc1"`, if any.
+    pub pre: Option<String>,
+}
+
+impl SemVer {
+    /// Parses a version string, returning `None` for anything that does
+    /// not start with at least `MAJOR.MINOR` — e.g. `"UNKNOWN"` or a bare
+    /// git hash.
+    pub fn parse(version: &str) -> Option<Self> {
+        let version = version.trim();
+        let version = version.strip_prefix('v').unwrap_or(version);
+        let (core, pre) = match version.split_once(['-', '+', ' ']) {
+            Some((core, rest)) => (core, Some(rest)),
+            None => (version, None),
+        };
+
+        let mut parts = core.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let (minor, attached) = split_number(parts.next()?)?;
+        let (patch, attached) = match (attached, parts.next()) {
+            ("", Some(patch)) => split_number(patch)?,
+            ("", None) => (0, ""),
+            (_, Some(_)) => return None,
+            (tag, None) => (0, tag),
+        };
+        if parts.next().is_some() {
+            return None;
+        }
+
+        let pre = match (attached, pre) {
+            ("", None) => None,
+            ("", Some(tag)) | (tag, None) => Some(tag.to_owned()),
+            (_, Some(_)) => return None,
+        };
+        if pre.as_deref() == Some("") {
+            return None;
+        }
+        Some(Self {
+            major,
+            minor,
+            patch,
+            pre,
+        })
+    }
+
+    /// `true` when the numeric components are at least
+    /// `major.minor.patch`. Pre-release tags are ignored, so a `2.0b4`
+    /// build counts as 2.0.0 for capability checks.
+    pub fn at_least(&self, major: u32, minor: u32, patch: u32) -> bool {
+        (self.major, self.minor, self.patch) >= (major, minor, patch)
+    }
+}
+
+/// Splits a leading decimal number off `part`, returning it with the
+/// remainder. `None` when `part` does not start with a digit.
+fn split_number(part: &str) -> Option<(u32, &str)> {
+    let end = part
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(part.len());
+    let number = part[..end].parse().ok()?;
+    Some((number, &part[end..]))
+}
+
+impl fmt::Display for SemVer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        match &self.pre {
+            Some(pre) => write!(f, "-{pre}"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Returns the linked cpdb-libs version parsed into components, or `None`
+/// when the library reports no version or a non-release string such as
+/// `"UNKNOWN"` or a git hash.
+///
+/// Use this for capability gating; [`version`] still returns the raw
+/// string for display.
+pub fn parsed_version() -> Option<SemVer> {
+    version().ok().as_deref().and_then(SemVer::parse)
+}
+
 /// Initialises cpdb-libs.
 ///
 /// Idempotent — safe to call multiple times. Call once at process startup
@@ -44,8 +143,10 @@ pub fn system_config_dir() -> Result<String> {
 
 /// Resolves `path` to an absolute path, expanding `~` and relative
 /// segments according to cpdb-libs' rules.
-pub fn absolute_path(path: &str) -> Result<String> {
-    let c_path = CString::new(path)?;
+///
+/// A path containing a NUL byte fails with [`CpdbError::InvalidPath`].
+pub fn absolute_path(path: impl AsRef<Path>) -> Result<String> {
+    let c_path = util::path_to_cstring(path.as_ref())?;
     // SAFETY: returns a `g_strdup`'d string we own.
     unsafe { util::cstr_to_string_and_g_free(ffi::cpdbGetAbsolutePath(c_path.as_ptr())) }
 }
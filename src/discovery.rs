@@ -0,0 +1,314 @@
+//! Buffered collection of printer-discovery events.
+//!
+//! A frontend built with [`FrontendBuilder::event_buffer`] records every
+//! printer-update callback as an owned [`DiscoveryEvent`] that the
+//! application drains at its own pace via [`Frontend::drain_events`].
+//! [`BufferPolicy`] decides what happens when events arrive faster than
+//! they are drained.
+//!
+//! # Choosing a policy
+//!
+//! - [`BufferPolicy::Unbounded`] (the default) never loses or delays an
+//!   event, but memory grows with the backlog. On a fleet of thousands of
+//!   printers an undrained buffer holds one record per event.
+//! - [`BufferPolicy::DropOldest`] caps memory and never stalls cpdb-libs,
+//!   at the cost of discarding the oldest records once full. Suited to
+//!   consumers that only care about recent state and re-read the printer
+//!   table anyway. [`Frontend::dropped_events`] reports the loss.
+//! - [`BufferPolicy::Block`] caps memory without losing events by making
+//!   the callback wait until the buffer has room. This stalls cpdb-libs'
+//!   D-Bus listener thread, delaying every other callback on it. Only use
+//!   it when a different thread drains the buffer: draining from the
+//!   thread that delivers callbacks deadlocks.
+//!
+//! # Cancelling discovery
+//!
+//! [`Frontend::discover_printers_until`] takes a [`CancellationHandle`];
+//! cancelling it from any thread makes discovery return early with the
+//! printers found so far. Cancellation is not an error.
+//!
+//! [`FrontendBuilder::event_buffer`]: crate::FrontendBuilder::event_buffer
+//! [`Frontend::discover_printers_until`]: crate::Frontend::discover_printers_until
+//! [`Frontend::drain_events`]: crate::Frontend::drain_events
+//! [`Frontend::dropped_events`]: crate::Frontend::dropped_events
+
+use crate::callbacks::PrinterUpdate;
+use crate::printer::Printer;
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+
+/// What to do when a bounded event buffer is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BufferPolicy {
+    /// Keep every event; memory is bounded only by how often the buffer
+    /// is drained.
+    #[default]
+    Unbounded,
+    /// Hold at most this many events, discarding the oldest to make room.
+    DropOldest(usize),
+    /// Hold at most this many events, blocking the callback until the
+    /// buffer is drained. See the [module docs](self) for the deadlock
+    /// caveat.
+    Block(usize),
+}
+
+/// A cloneable flag for cancelling discovery from another thread.
+///
+/// Every clone shares the same flag; cancelling any clone cancels all.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationHandle(Arc<AtomicBool>);
+
+impl CancellationHandle {
+    /// Creates a handle in the not-cancelled state.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests cancellation. Idempotent.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+
+    /// `true` once [`CancellationHandle::cancel`] has been called on any
+    /// clone.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+}
+
+impl From<Arc<AtomicBool>> for CancellationHandle {
+    /// Wraps an existing flag, for callers that already share one.
+    fn from(flag: Arc<AtomicBool>) -> Self {
+        Self(flag)
+    }
+}
+
+/// An owned record of one printer-update callback.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoveryEvent {
+    /// What changed.
+    pub update: PrinterUpdate,
+    /// The printer's `id` field.
+    pub printer_id: String,
+    /// The backend the printer belongs to.
+    pub backend_name: String,
+    /// The printer's display name.
+    pub printer_name: String,
+}
+
+impl DiscoveryEvent {
+    /// Copies the identifying fields out of a borrowed printer.
+    pub(crate) fn from_printer(printer: &Printer<'_>, update: PrinterUpdate) -> Self {
+        Self {
+            update,
+            printer_id: printer.id().unwrap_or_default(),
+            backend_name: printer.backend_name().unwrap_or_default(),
+            printer_name: printer.name().unwrap_or_default(),
+        }
+    }
+}
+
+/// A printer left out of a discovery result because it could not be read.
+///
+/// Returned by [`Frontend::discover_printers_with_warnings`].
+///
+/// [`Frontend::discover_printers_with_warnings`]: crate::Frontend::discover_printers_with_warnings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoveryWarning {
+    /// The printer's id, lossily decoded; empty when missing.
+    pub printer_id: String,
+    /// The printer's backend, lossily decoded; empty when missing.
+    pub backend_name: String,
+    /// Why the printer was skipped.
+    pub reason: String,
+}
+
+impl DiscoveryWarning {
+    pub(crate) fn new(printer: &Printer<'_>, reason: String) -> Self {
+        Self {
+            printer_id: printer.id().unwrap_or_default(),
+            backend_name: printer.backend_name().unwrap_or_default(),
+            reason,
+        }
+    }
+}
+
+impl fmt::Display for DiscoveryWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "skipped printer '{}' on backend '{}': {}",
+            self.printer_id, self.backend_name, self.reason
+        )
+    }
+}
+
+/// The shared queue behind a frontend's event buffer.
+#[derive(Debug, Default)]
+pub(crate) struct EventBuffer {
+    policy: BufferPolicy,
+    state: Mutex<BufferState>,
+    not_full: Condvar,
+    dropped: AtomicU64,
+}
+
+#[derive(Debug, Default)]
+struct BufferState {
+    queue: VecDeque<DiscoveryEvent>,
+    closed: bool,
+}
+
+impl EventBuffer {
+    pub(crate) fn new(policy: BufferPolicy) -> Self {
+        Self {
+            policy,
+            ..Self::default()
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BufferState> {
+        self.state.lock().unwrap_or_else(|p| p.into_inner())
+    }
+
+    /// Records an event according to the buffer's policy.
+    pub(crate) fn push(&self, event: DiscoveryEvent) {
+        let mut state = self.lock();
+        match self.policy {
+            BufferPolicy::Unbounded => {}
+            BufferPolicy::DropOldest(cap) => {
+                if cap == 0 {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                    return;
+                }
+                while state.queue.len() >= cap {
+                    state.queue.pop_front();
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            BufferPolicy::Block(cap) => {
+                let cap = cap.max(1);
+                while state.queue.len() >= cap && !state.closed {
+                    state = self.not_full.wait(state).unwrap_or_else(|p| p.into_inner());
+                }
+                if state.closed {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                    return;
+                }
+            }
+        }
+        state.queue.push_back(event);
+    }
+
+    /// Removes and returns every buffered event, oldest first.
+    pub(crate) fn drain(&self) -> Vec<DiscoveryEvent> {
+        let events: Vec<_> = self.lock().queue.drain(..).collect();
+        self.not_full.notify_all();
+        events
+    }
+
+    /// Number of events discarded so far.
+    pub(crate) fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Wakes any blocked producer and makes future pushes no-ops. Called
+    /// when the owning frontend is dropped.
+    pub(crate) fn close(&self) {
+        self.lock().closed = true;
+        self.not_full.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use std::time::Duration;
+
+    fn event(n: usize) -> DiscoveryEvent {
+        DiscoveryEvent {
+            update: PrinterUpdate::Added,
+            printer_id: format!("p{n}"),
+            backend_name: "TEST".into(),
+            printer_name: format!("Printer {n}"),
+        }
+    }
+
+    fn ids(events: &[DiscoveryEvent]) -> Vec<&str> {
+        events.iter().map(|e| e.printer_id.as_str()).collect()
+    }
+
+    #[test]
+    fn unbounded_keeps_everything_in_order() {
+        let buf = EventBuffer::new(BufferPolicy::Unbounded);
+        for n in 0..100 {
+            buf.push(event(n));
+        }
+        let events = buf.drain();
+        assert_eq!(events.len(), 100);
+        assert_eq!(events[0].printer_id, "p0");
+        assert_eq!(buf.dropped(), 0);
+        assert!(buf.drain().is_empty());
+    }
+
+    #[test]
+    fn drop_oldest_keeps_most_recent() {
+        let buf = EventBuffer::new(BufferPolicy::DropOldest(3));
+        for n in 0..5 {
+            buf.push(event(n));
+        }
+        assert_eq!(ids(&buf.drain()), ["p2", "p3", "p4"]);
+        assert_eq!(buf.dropped(), 2);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn block_waits_for_drain() {
+        let buf = Arc::new(EventBuffer::new(BufferPolicy::Block(1)));
+        buf.push(event(0));
+
+        let producer = {
+            let buf = Arc::clone(&buf);
+            thread::spawn(move || buf.push(event(1)))
+        };
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(ids(&buf.drain()), ["p0"]);
+
+        producer.join().unwrap();
+        assert_eq!(ids(&buf.drain()), ["p1"]);
+        assert_eq!(buf.dropped(), 0);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn close_releases_blocked_producer() {
+        let buf = Arc::new(EventBuffer::new(BufferPolicy::Block(1)));
+        buf.push(event(0));
+
+        let producer = {
+            let buf = Arc::clone(&buf);
+            thread::spawn(move || buf.push(event(1)))
+        };
+        thread::sleep(Duration::from_millis(20));
+        buf.close();
+
+        producer.join().unwrap();
+        assert_eq!(buf.dropped(), 1);
+    }
+
+    #[test]
+    fn cancellation_is_shared_between_clones() {
+        let handle = CancellationHandle::new();
+        let other = handle.clone();
+        assert!(!other.is_cancelled());
+        handle.cancel();
+        assert!(other.is_cancelled());
+
+        let flag = Arc::new(AtomicBool::new(false));
+        let wrapped = CancellationHandle::from(Arc::clone(&flag));
+        flag.store(true, Ordering::SeqCst);
+        assert!(wrapped.is_cancelled());
+    }
+}
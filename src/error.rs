@@ -51,6 +51,11 @@ pub enum CpdbError {
     #[error("Nul byte in string: {0}")]
     NulError(#[from] NulError),
 
+    /// A file path could not be passed to cpdb-libs (it contains a NUL
+    /// byte). Carries the path as displayed.
+    #[error("Invalid path {0}: contains a NUL byte")]
+    InvalidPath(String),
+
     /// An I/O error bubbled up from std::io.
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
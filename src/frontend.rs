@@ -10,18 +10,46 @@
 //! `&self` for ergonomics but mutate the underlying C state, and
 //! cpdb-libs does not lock internally. If you need concurrent access,
 //! wrap the frontend in a [`std::sync::Mutex`].
+//!
+//! # Instance names
+//!
+//! Every frontend carries an instance name, set via
+//! [`FrontendBuilder::instance_name`] or generated as
+//! `cpdb-rs-<pid>-<n>` (`cpdb-rs-tmp-…` for [`Frontend::new_temporary`]).
+//! The cpdb-libs 2.x constructor takes no name, so nothing is requested
+//! on the bus: the session connection is shared process-wide and
+//! identified by its bus-assigned unique name. The instance name labels
+//! the frontend in this crate's log output.
+//!
+//! Generated names never collide, so frontends created in parallel — e.g.
+//! by concurrently running tests — get distinct log labels without
+//! configuration. Explicit names are not checked: two frontends may share
+//! one, since nothing on the bus depends on it.
 
-use crate::callbacks::{self, PrinterUpdate};
+use crate::callbacks::{self, PrinterEvent, PrinterObserver, PrinterUpdate};
+use crate::discovery::{
+    BufferPolicy, CancellationHandle, DiscoveryEvent, DiscoveryWarning, EventBuffer,
+};
 use crate::error::{CpdbError, Result};
 use crate::ffi;
-use crate::printer::Printer;
+use crate::ipp::PrinterState;
+use crate::printer::{self, Printer};
+#[cfg(feature = "serde")]
+use crate::snapshot::{FleetSnapshot, PrinterSnapshot};
+use std::collections::HashMap;
 use std::ffi::{CStr, CString};
 use std::mem::MaybeUninit;
 use std::ptr::NonNull;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 
 /// Safe wrapper around `cpdb_frontend_obj_t`.
 pub struct Frontend {
     raw: NonNull<ffi::cpdb_frontend_obj_t>,
+    instance_name: String,
+    temporary: bool,
+    events: Option<Arc<EventBuffer>>,
 }
 
 // SAFETY: `Frontend` owns its `cpdb_frontend_obj_t *`. Moving it across
@@ -37,6 +65,27 @@ impl Frontend {
         Self::new_internal(None)
     }
 
+    /// Returns a [`FrontendBuilder`] for configuring a frontend before
+    /// construction.
+    pub fn builder() -> FrontendBuilder {
+        FrontendBuilder::new()
+    }
+
+    /// Creates a short-lived frontend for one-shot processes such as CLI
+    /// invocations.
+    ///
+    /// The frontend gets a unique, anonymous instance name, skips loading
+    /// the last-saved settings, and registers no printer observer, so
+    /// repeated runs leave nothing behind in the observer registry or in
+    /// the settings applied to discovered printers.
+    ///
+    /// This is unsuitable for long-running event listeners: no observer
+    /// is installed, so add/remove/state-change events are dropped. Use
+    /// [`FrontendBuilder`] with a named instance and an observer for those.
+    pub fn new_temporary() -> Result<Self> {
+        FrontendBuilder::new().temporary(true).build()
+    }
+
     /// Creates a new frontend with a raw printer-update callback.
     ///
     /// Prefer [`Frontend::new_with_observer`] for closure-based callbacks.
@@ -65,19 +114,95 @@ impl Frontend {
     where
         F: FnMut(&Printer<'_>, PrinterUpdate) + Send + 'static,
     {
-        let cb: ffi::cpdb_printer_callback = Some(callbacks::printer_trampoline);
-        let frontend = Self::new_internal(cb)?;
-        callbacks::register_printer_observer(frontend.raw.as_ptr(), Box::new(observer));
-        Ok(frontend)
+        FrontendBuilder::new().observer(observer).build()
+    }
+
+    /// Installs `observer` as this frontend's primary printer observer.
+    ///
+    /// There is one primary slot per frontend: calling this again replaces
+    /// the previous observer, which is dropped immediately (a warning is
+    /// logged so accidental re-registration is visible). Use
+    /// [`Frontend::add_printer_listener`] to attach several independent
+    /// consumers instead.
+    ///
+    /// This points the frontend's C callback at the crate's trampoline,
+    /// replacing any raw callback given to [`Frontend::new_with_callback`].
+    /// Register before [`Frontend::connect_to_dbus`] so no events are
+    /// missed.
+    pub fn set_observer<F>(&self, observer: F)
+    where
+        F: FnMut(&Printer<'_>, PrinterUpdate) + Send + 'static,
+    {
+        self.install_trampoline();
+        callbacks::register_printer_observer(self.raw.as_ptr(), Box::new(observer));
+    }
+
+    /// Adds a printer listener alongside the primary observer.
+    ///
+    /// Listeners accumulate and are invoked, in registration order, after
+    /// the primary observer for every event. They stay registered until
+    /// the frontend is dropped. The same callback caveats as
+    /// [`Frontend::set_observer`] apply.
+    pub fn add_printer_listener<F>(&self, listener: F)
+    where
+        F: FnMut(&Printer<'_>, PrinterUpdate) + Send + 'static,
+    {
+        self.install_trampoline();
+        callbacks::add_printer_listener(self.raw.as_ptr(), Box::new(listener));
+    }
+
+    /// Adds a listener that receives each printer callback as a
+    /// [`PrinterEvent`].
+    ///
+    /// This is [`Frontend::add_printer_listener`] with the payload already
+    /// matched up to the kind of change: added and state-changed printers
+    /// arrive as borrowed [`Printer`] handles, removed printers as their
+    /// id, because cpdb-libs frees the object once the callback returns.
+    ///
+    /// The handler runs on the thread driving cpdb-libs' GLib main loop,
+    /// i.e. its D-Bus listener thread, not on the caller's thread. The
+    /// `Printer` handles are only valid for the duration of the call.
+    /// Panics are caught and absorbed so they never unwind into C.
+    pub fn on_printer_event<F>(&self, mut handler: F)
+    where
+        F: FnMut(PrinterEvent<'_>) + Send + 'static,
+    {
+        self.add_printer_listener(move |printer, update| {
+            handler(PrinterEvent::new(printer, update));
+        });
+    }
+
+    /// Routes the C printer callback through the closure registry.
+    fn install_trampoline(&self) {
+        // SAFETY: pointer is non-null; cpdb-libs reads `printer_cb` afresh
+        // for every event, so updating the field is all that is needed.
+        unsafe { (*self.raw.as_ptr()).printer_cb = Some(callbacks::printer_trampoline) };
     }
 
     fn new_internal(cb: ffi::cpdb_printer_callback) -> Result<Self> {
+        Self::construct(cb, default_instance_name("cpdb-rs"), false)
+    }
+
+    fn construct(
+        cb: ffi::cpdb_printer_callback,
+        instance_name: String,
+        temporary: bool,
+    ) -> Result<Self> {
         // SAFETY: `cpdbGetNewFrontendObj` is a constructor; the callback may
         // be null.
         let raw = unsafe { ffi::cpdbGetNewFrontendObj(cb) };
-        NonNull::new(raw)
-            .map(|raw| Self { raw })
-            .ok_or_else(|| CpdbError::FrontendError("cpdbGetNewFrontendObj returned null".into()))
+        let Some(raw) = NonNull::new(raw) else {
+            return Err(CpdbError::FrontendError(
+                "cpdbGetNewFrontendObj returned null".into(),
+            ));
+        };
+        log::debug!("created frontend '{instance_name}'");
+        Ok(Self {
+            raw,
+            instance_name,
+            temporary,
+            events: None,
+        })
     }
 
     /// Wraps an already-allocated frontend object.
@@ -87,9 +212,18 @@ impl Frontend {
     /// cpdb-libs, not aliased by any other Rust handle, and not yet freed.
     /// Ownership transfers to the returned `Frontend`.
     pub unsafe fn from_raw(raw: *mut ffi::cpdb_frontend_obj_t) -> Result<Self> {
-        NonNull::new(raw)
-            .map(|raw| Self { raw })
-            .ok_or(CpdbError::NullPointer)
+        let raw = NonNull::new(raw).ok_or(CpdbError::NullPointer)?;
+        Ok(Self::adopt(raw))
+    }
+
+    /// Wraps a frontend cpdb-libs created for us, under a generated name.
+    fn adopt(raw: NonNull<ffi::cpdb_frontend_obj_t>) -> Self {
+        Self {
+            raw,
+            instance_name: default_instance_name("cpdb-rs"),
+            temporary: false,
+            events: None,
+        }
     }
 
     /// Returns the raw pointer for use within this crate.
@@ -98,6 +232,37 @@ impl Frontend {
         self.raw.as_ptr()
     }
 
+    /// The instance name this frontend was built with.
+    ///
+    /// See [the module docs](self#instance-names) for what the name is
+    /// used for.
+    pub fn instance_name(&self) -> &str {
+        &self.instance_name
+    }
+
+    /// `true` when this frontend was created via [`Frontend::new_temporary`]
+    /// (or [`FrontendBuilder::temporary`]).
+    pub fn is_temporary(&self) -> bool {
+        self.temporary
+    }
+
+    /// Removes and returns every buffered discovery event, oldest first.
+    ///
+    /// Always empty unless the frontend was built with
+    /// [`FrontendBuilder::event_buffer`]. Draining also wakes a callback
+    /// blocked under [`BufferPolicy::Block`].
+    pub fn drain_events(&self) -> Vec<DiscoveryEvent> {
+        self.events
+            .as_ref()
+            .map(|buf| buf.drain())
+            .unwrap_or_default()
+    }
+
+    /// Number of discovery events discarded by the buffer policy so far.
+    pub fn dropped_events(&self) -> u64 {
+        self.events.as_ref().map_or(0, |buf| buf.dropped())
+    }
+
     // ─── Lifecycle ───────────────────────────────────────────────────────────
 
     /// Tells the frontend to ignore the previously saved settings file.
@@ -109,12 +274,29 @@ impl Frontend {
     }
 
     /// Connects to the session D-Bus and activates the print backends.
+    ///
+    /// `cpdbConnectToDBus` reports nothing; success is verified by checking
+    /// that the frontend now holds a connection. Fails with
+    /// [`CpdbError::FrontendError`] when it does not, e.g. without a
+    /// session bus.
     pub fn connect_to_dbus(&self) -> Result<()> {
         // SAFETY: pointer is non-null.
         unsafe { ffi::cpdbConnectToDBus(self.raw.as_ptr()) };
+        if !self.has_connection() {
+            return Err(CpdbError::FrontendError(format!(
+                "frontend '{}' could not connect to the session bus",
+                self.instance_name
+            )));
+        }
         Ok(())
     }
 
+    /// `true` when the frontend object holds a D-Bus connection.
+    fn has_connection(&self) -> bool {
+        // SAFETY: plain field read of a live frontend object.
+        !unsafe { (*self.raw.as_ptr()).connection }.is_null()
+    }
+
     /// Returns `true` when cpdb-libs currently holds a live D-Bus connection.
     ///
     /// This consults `cpdbGetDbusConnection`, which is a process-global —
@@ -127,9 +309,24 @@ impl Frontend {
     }
 
     /// Disconnects from D-Bus.
+    ///
+    /// Disconnecting a frontend that holds no connection does nothing.
+    /// Otherwise `cpdbDisconnectFromDBus` reports nothing, so success is
+    /// verified by checking that the frontend no longer holds a
+    /// connection; fails with [`CpdbError::FrontendError`] when it still
+    /// does.
     pub fn disconnect_from_dbus(&self) -> Result<()> {
+        if !self.has_connection() {
+            return Ok(());
+        }
         // SAFETY: pointer is non-null.
         unsafe { ffi::cpdbDisconnectFromDBus(self.raw.as_ptr()) };
+        if self.has_connection() {
+            return Err(CpdbError::FrontendError(format!(
+                "frontend '{}' is still connected to D-Bus after disconnecting",
+                self.instance_name
+            )));
+        }
         Ok(())
     }
 
@@ -151,12 +348,65 @@ impl Frontend {
         unsafe { ffi::cpdbStopBackendListRefreshing(self.raw.as_ptr()) };
     }
 
+    /// Reports which backends are online, for "CUPS ready, FILE ready"
+    /// style progress during startup.
+    ///
+    /// Expected backends are the service files installed in the backend
+    /// info directory cpdb-libs was built with, resolved at build time
+    /// from `cpdb.pc` (override with the `CPDB_BACKEND_INFO_DIR` env var
+    /// when building; `/usr/share/print-backends` when neither is
+    /// available). A backend counts as active once it appears in the
+    /// frontend's backend table, which cpdb-libs fills as each one is
+    /// activated after [`Frontend::connect_to_dbus`]. Active backends
+    /// without an installed service file are included too. The list is
+    /// sorted by name. Poll it; cpdb-libs emits no per-backend activation
+    /// signal.
+    pub fn backend_status(&self) -> Vec<(String, bool)> {
+        let installed = std::fs::read_dir(BACKEND_INFO_DIR)
+            .map(|dir| {
+                dir.filter_map(|entry| entry.ok())
+                    .filter_map(|entry| entry.file_name().into_string().ok())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        merge_backend_status(&installed, &self.active_backends())
+    }
+
+    /// Names of the backends in the frontend's backend table.
+    fn active_backends(&self) -> Vec<String> {
+        // SAFETY: dereferencing the backend table field is sound; we only
+        // read its keys.
+        let table = unsafe { (*self.raw.as_ptr()).backend } as *mut glib_sys::GHashTable;
+        if table.is_null() {
+            return Vec::new();
+        }
+
+        let mut names = Vec::new();
+        // SAFETY: see `printer_table_ptrs`; keys are NUL-terminated
+        // backend names owned by the table.
+        unsafe {
+            let mut iter = MaybeUninit::<glib_sys::GHashTableIter>::uninit();
+            glib_sys::g_hash_table_iter_init(iter.as_mut_ptr(), table);
+            let mut iter = iter.assume_init();
+
+            let mut key: glib_sys::gpointer = std::ptr::null_mut();
+            let mut value: glib_sys::gpointer = std::ptr::null_mut();
+            while glib_sys::g_hash_table_iter_next(&mut iter, &mut key, &mut value) != 0 {
+                if !key.is_null() {
+                    let name = CStr::from_ptr(key as *const libc::c_char);
+                    names.push(name.to_string_lossy().into_owned());
+                }
+            }
+        }
+        names
+    }
+
     /// Starts the printer-listing flow (`cpdbStartListingPrinters`), creating
     /// a fresh frontend bound to the supplied callback.
     pub fn start_listing(cb: ffi::cpdb_printer_callback) -> Result<Self> {
         // SAFETY: callback may be null per upstream docs.
         let raw = unsafe { ffi::cpdbStartListingPrinters(cb) };
-        NonNull::new(raw).map(|raw| Self { raw }).ok_or_else(|| {
+        NonNull::new(raw).map(Self::adopt).ok_or_else(|| {
             CpdbError::FrontendError("cpdbStartListingPrinters returned null".into())
         })
     }
@@ -167,6 +417,80 @@ impl Frontend {
         unsafe { ffi::cpdbStopListingPrinters(self.raw.as_ptr()) };
     }
 
+    // ─── Default printer ─────────────────────────────────────────────────────
+
+    /// The default printer (`cpdbGetDefaultPrinter`), or `None` when none is
+    /// configured.
+    ///
+    /// cpdb-libs consults the user's default first, then the system's,
+    /// among the printers this frontend has discovered.
+    pub fn default_printer(&self) -> Result<Option<Printer<'_>>> {
+        // SAFETY: pointer is non-null.
+        let raw = unsafe { ffi::cpdbGetDefaultPrinter(self.raw.as_ptr()) };
+        self.wrap_optional(raw)
+    }
+
+    /// The default printer of one backend, or `None` when that backend has
+    /// none or is not active.
+    pub fn default_printer_for_backend(&self, backend_name: &str) -> Result<Option<Printer<'_>>> {
+        let c_backend = CString::new(backend_name)?;
+        // SAFETY: pointer is non-null; the CString outlives the call.
+        let raw =
+            unsafe { ffi::cpdbGetDefaultPrinterForBackend(self.raw.as_ptr(), c_backend.as_ptr()) };
+        self.wrap_optional(raw)
+    }
+
+    /// Makes `printer` the user's default (`cpdbSetUserDefaultPrinter`).
+    ///
+    /// Fails with [`CpdbError::InvalidPrinter`] when `printer` is no longer
+    /// in a frontend's table (see [`Printer::is_valid`]) and with
+    /// [`CpdbError::BackendError`] when cpdb-libs reports failure.
+    pub fn set_user_default(&self, printer: &Printer<'_>) -> Result<()> {
+        Self::check_default_target(printer)?;
+        // SAFETY: the printer pointer is non-null and still live.
+        let ok = unsafe { ffi::cpdbSetUserDefaultPrinter(printer.as_raw()) } != 0;
+        if !ok {
+            return Err(CpdbError::BackendError(format!(
+                "could not make '{}' the user default printer",
+                printer.name().unwrap_or_default()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Makes `printer` the system-wide default
+    /// (`cpdbSetSystemDefaultPrinter`).
+    ///
+    /// Changing the system default usually needs administrator rights; a
+    /// refusal surfaces as [`CpdbError::BackendError`]. Fails with
+    /// [`CpdbError::InvalidPrinter`] when `printer` is no longer valid.
+    pub fn set_system_default(&self, printer: &Printer<'_>) -> Result<()> {
+        Self::check_default_target(printer)?;
+        // SAFETY: the printer pointer is non-null and still live.
+        let ok = unsafe { ffi::cpdbSetSystemDefaultPrinter(printer.as_raw()) } != 0;
+        if !ok {
+            return Err(CpdbError::BackendError(format!(
+                "could not make '{}' the system default printer (insufficient permissions?)",
+                printer.name().unwrap_or_default()
+            )));
+        }
+        Ok(())
+    }
+
+    fn check_default_target(printer: &Printer<'_>) -> Result<()> {
+        if !printer.is_valid() {
+            return Err(CpdbError::InvalidPrinter);
+        }
+        Ok(())
+    }
+
+    fn wrap_optional(&self, raw: *mut ffi::cpdb_printer_obj_t) -> Result<Option<Printer<'_>>> {
+        if raw.is_null() {
+            return Ok(None);
+        }
+        Printer::from_frontend_table(raw, self.raw.as_ptr()).map(Some)
+    }
+
     // ─── Visibility toggles ──────────────────────────────────────────────────
 
     /// Hides remote printers from subsequent listings.
@@ -210,34 +534,28 @@ impl Frontend {
                 "printer '{printer_id}' on backend '{backend_name}'"
             )))
         } else {
-            Printer::from_raw_borrowed(raw)
+            Printer::from_frontend_table(raw, self.raw.as_ptr())
         }
     }
 
     /// Returns the user-default printer, if one is set.
+    ///
+    /// Prefer [`Frontend::default_printer`], which reports "no default" as
+    /// `None` rather than an error.
     pub fn get_default_printer(&self) -> Result<Printer<'_>> {
-        // SAFETY: pointer is non-null.
-        let raw = unsafe { ffi::cpdbGetDefaultPrinter(self.raw.as_ptr()) };
-        if raw.is_null() {
-            Err(CpdbError::NotFound("default printer".into()))
-        } else {
-            Printer::from_raw_borrowed(raw)
-        }
+        self.default_printer()?
+            .ok_or_else(|| CpdbError::NotFound("default printer".into()))
     }
 
     /// Returns the default printer for a specific backend, if one is set.
+    ///
+    /// Prefer [`Frontend::default_printer_for_backend`], which reports "no
+    /// default" as `None` rather than an error.
     pub fn get_default_printer_for_backend(&self, backend_name: &str) -> Result<Printer<'_>> {
-        let c_backend = CString::new(backend_name)?;
-        // SAFETY: pointer is non-null; the CString outlives the call.
-        let raw =
-            unsafe { ffi::cpdbGetDefaultPrinterForBackend(self.raw.as_ptr(), c_backend.as_ptr()) };
-        if raw.is_null() {
-            Err(CpdbError::NotFound(format!(
-                "default printer for backend '{backend_name}'"
-            )))
-        } else {
-            Printer::from_raw_borrowed(raw)
-        }
+        self.default_printer_for_backend(backend_name)?
+            .ok_or_else(|| {
+                CpdbError::NotFound(format!("default printer for backend '{backend_name}'"))
+            })
     }
 
     /// Asks every backend to refresh its printer list.
@@ -246,6 +564,79 @@ impl Frontend {
         unsafe { ffi::cpdbGetAllPrinters(self.raw.as_ptr()) };
     }
 
+    /// Refreshes every backend and collects printers for up to `timeout`.
+    ///
+    /// Pumps the default GLib main context while waiting so that
+    /// printer-added signals are processed, then returns every printer in
+    /// the table. Equivalent to [`Frontend::discover_printers_until`] with
+    /// a handle that is never cancelled.
+    pub fn discover_printers(&self, timeout: Duration) -> Result<Vec<Printer<'_>>> {
+        self.discover_printers_until(timeout, &CancellationHandle::new())
+    }
+
+    /// Like [`Frontend::discover_printers`], but stops early once `cancel`
+    /// is cancelled.
+    ///
+    /// The handle is checked between main-loop iterations, so cancellation
+    /// takes effect within a few milliseconds. A cancelled discovery
+    /// returns the printers found so far — it is not an error.
+    pub fn discover_printers_until(
+        &self,
+        timeout: Duration,
+        cancel: &CancellationHandle,
+    ) -> Result<Vec<Printer<'_>>> {
+        self.pump_discovery(timeout, cancel);
+        self.get_printers()
+    }
+
+    /// Like [`Frontend::discover_printers`], but separates printers whose
+    /// identifying fields cannot be read from the rest.
+    ///
+    /// A printer with a missing or non-UTF-8 id, name, or backend name is
+    /// left out of the returned list and reported as a
+    /// [`DiscoveryWarning`] instead, so one broken queue does not hide the
+    /// others.
+    pub fn discover_printers_with_warnings(
+        &self,
+        timeout: Duration,
+    ) -> Result<(Vec<Printer<'_>>, Vec<DiscoveryWarning>)> {
+        self.pump_discovery(timeout, &CancellationHandle::new());
+        let mut printers = Vec::new();
+        let mut warnings = Vec::new();
+        for printer in self.iter_known_printers() {
+            match printer.check_identity() {
+                Ok(()) => printers.push(printer),
+                Err(reason) => warnings.push(DiscoveryWarning::new(&printer, reason)),
+            }
+        }
+        Ok((printers, warnings))
+    }
+
+    /// Refreshes every backend and pumps the default main context until
+    /// `timeout` elapses or `cancel` fires.
+    fn pump_discovery(&self, timeout: Duration, cancel: &CancellationHandle) {
+        const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+        let deadline = Instant::now() + timeout;
+        if !cancel.is_cancelled() {
+            self.refresh_printers();
+        }
+        while !cancel.is_cancelled() && Instant::now() < deadline {
+            // SAFETY: a null context selects the global default context;
+            // FALSE makes the iteration non-blocking.
+            let dispatched =
+                unsafe { glib_sys::g_main_context_iteration(std::ptr::null_mut(), 0) } != 0;
+            if !dispatched {
+                std::thread::sleep(
+                    POLL_INTERVAL.min(deadline.saturating_duration_since(Instant::now())),
+                );
+            }
+        }
+        if cancel.is_cancelled() {
+            log::debug!("discovery on frontend '{}' cancelled", self.instance_name);
+        }
+    }
+
     /// Adds an owned printer to the frontend's table.
     ///
     /// Takes ownership of the printer — cpdb-libs becomes responsible for
@@ -303,41 +694,101 @@ impl Frontend {
 
     /// Returns every printer currently known by walking the internal hash table.
     ///
-    /// The returned printers borrow from `self`.
+    /// Like [`Frontend::iter_known_printers`], this reads the table as it
+    /// stands and does not ask the backends for anything; call
+    /// [`Frontend::refresh_printers`] first to trigger discovery.
+    ///
+    /// The returned printers borrow from `self`: the C objects stay owned
+    /// by the frontend's table and are never freed by Rust, and the borrow
+    /// checker keeps them from outliving the frontend.
     pub fn get_printers(&self) -> Result<Vec<Printer<'_>>> {
-        // SAFETY: dereferencing the printer table field is sound; we only
-        // read borrowed pointers and never write through them.
-        let table = unsafe { (*self.raw.as_ptr()).printer } as *mut glib_sys::GHashTable;
-        if table.is_null() {
-            return Ok(Vec::new());
+        Ok(self.iter_known_printers().collect())
+    }
+
+    /// Iterates over the printers already in the frontend's table.
+    ///
+    /// The table is populated by earlier discovery and printer-update
+    /// callbacks; reading it has no side effects — no D-Bus traffic and no
+    /// backend enumeration. This is the right call from an update handler
+    /// that only needs to re-render the current list. Use
+    /// [`Frontend::refresh_printers`] to go and discover.
+    ///
+    /// The iterator walks a snapshot taken when this is called, so printers
+    /// added afterwards are not seen.
+    pub fn iter_known_printers(&self) -> impl Iterator<Item = Printer<'_>> {
+        // SAFETY: `self.raw` is live; `Frontend` is not `Sync`, so the
+        // table is not mutated through it during the walk.
+        unsafe { printer_table_ptrs(self.raw.as_ptr()) }
+            .into_iter()
+            .filter_map(|raw| Printer::from_frontend_table(raw, self.raw.as_ptr()).ok())
+    }
+
+    /// Counts the known printers by state, e.g. for "3 idle, 1 stopped".
+    ///
+    /// Reads each printer's cached state as of the last discovery or
+    /// state-change event; no backend is contacted. States that do not
+    /// parse as idle, processing, or stopped are counted under
+    /// [`PrinterState::Unknown`]. Only states with at least one printer
+    /// appear in the map.
+    pub fn state_summary(&self) -> Result<HashMap<PrinterState, usize>> {
+        let mut summary = HashMap::new();
+        for printer in self.iter_known_printers() {
+            let state = PrinterState::parse(&printer.cached_state()?);
+            *summary.entry(state).or_insert(0) += 1;
         }
+        Ok(summary)
+    }
 
-        let mut printers: Vec<Printer<'_>> = Vec::new();
-        // SAFETY: iterator is initialised on the stack and iterated
-        // synchronously; the table is not mutated during this loop.
-        unsafe {
-            let mut iter = MaybeUninit::<glib_sys::GHashTableIter>::uninit();
-            glib_sys::g_hash_table_iter_init(iter.as_mut_ptr(), table);
-            let mut iter = iter.assume_init();
+    /// Captures every known printer and its capabilities as a
+    /// serializable [`FleetSnapshot`], for persisting and later comparison
+    /// with [`FleetSnapshot::diff`].
+    ///
+    /// Reads the printer table like [`Frontend::iter_known_printers`] and
+    /// captures each printer with [`PrinterSnapshot::capture`]. A printer
+    /// without an option table is included with no options; only one
+    /// whose id or backend name is not valid UTF-8 is skipped.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn snapshot_fleet(&self) -> Result<FleetSnapshot> {
+        let printers = self
+            .iter_known_printers()
+            .filter_map(|printer| PrinterSnapshot::capture(&printer).ok())
+            .collect();
+        Ok(FleetSnapshot::from_printers(printers))
+    }
 
-            let mut key: glib_sys::gpointer = std::ptr::null_mut();
-            let mut value: glib_sys::gpointer = std::ptr::null_mut();
-            while glib_sys::g_hash_table_iter_next(&mut iter, &mut key, &mut value) != 0 {
-                let raw = value as *mut ffi::cpdb_printer_obj_t;
-                if let Ok(p) = Printer::from_raw_borrowed(raw) {
-                    printers.push(p);
-                }
-            }
+    /// Looks up a printer by id, optionally restricted to one backend.
+    ///
+    /// With a backend this is `cpdbFindPrinterObj`. cpdb-libs keys its
+    /// table by id *and* backend, so without one the table is searched for
+    /// the first printer whose id matches; ids are unique per backend, so
+    /// this can be ambiguous when several backends expose the same queue.
+    ///
+    /// Fails with [`CpdbError::InvalidPrinter`] when no printer matches.
+    /// Other errors, such as [`CpdbError::NulError`] for an id or backend
+    /// name containing a NUL byte, are returned unchanged.
+    pub fn get_printer<'f>(&'f self, id: &str, backend_name: Option<&str>) -> Result<Printer<'f>> {
+        if let Some(backend_name) = backend_name {
+            return self.find_printer(id, backend_name).map_err(|e| match e {
+                CpdbError::NotFound(_) => CpdbError::InvalidPrinter,
+                e => e,
+            });
         }
-        Ok(printers)
+        // No table id can contain a NUL byte; reject it like the backend
+        // path does rather than reporting a missing printer.
+        CString::new(id)?;
+        self.iter_known_printers()
+            .find(|printer| printer.id().is_ok_and(|p| p == id))
+            .ok_or(CpdbError::InvalidPrinter)
     }
 
-    /// Looks up the first printer whose `name` field equals the argument.
+    /// Looks up the first printer whose `name` field equals the argument,
+    /// for callers that know neither the id nor the backend.
     ///
     /// When multiple printers share a name across backends, the first one
     /// encountered during hash-table iteration wins. Prefer
-    /// [`Frontend::find_printer`] when you can supply a backend name.
-    pub fn get_printer<'f>(&'f self, name: &str) -> Result<Printer<'f>> {
+    /// [`Frontend::get_printer`] when you can supply an id.
+    pub fn get_printer_by_name<'f>(&'f self, name: &str) -> Result<Printer<'f>> {
         // SAFETY: dereferencing the printer table field is sound.
         let table = unsafe { (*self.raw.as_ptr()).printer } as *mut glib_sys::GHashTable;
         if table.is_null() {
@@ -345,7 +796,7 @@ impl Frontend {
         }
         let needle = name.as_bytes();
 
-        // SAFETY: see `get_printers`.
+        // SAFETY: see `printer_table_ptrs`.
         unsafe {
             let mut iter = MaybeUninit::<glib_sys::GHashTableIter>::uninit();
             glib_sys::g_hash_table_iter_init(iter.as_mut_ptr(), table);
@@ -363,7 +814,7 @@ impl Frontend {
                     continue;
                 }
                 if CStr::from_ptr(name_ptr).to_bytes() == needle {
-                    return Printer::from_raw_borrowed(raw);
+                    return Printer::from_frontend_table(raw, self.raw.as_ptr());
                 }
             }
         }
@@ -371,13 +822,256 @@ impl Frontend {
     }
 }
 
+// ─── Builder ─────────────────────────────────────────────────────────────────
+
+/// Configures a [`Frontend`] before construction.
+///
+/// ```no_run
+/// use cpdb_rs::{Frontend, PrinterUpdate};
+///
+/// let frontend = Frontend::builder()
+///     .instance_name("print-manager")
+///     .observer(|printer, update| {
+///         if update == PrinterUpdate::Added {
+///             println!("+ {}", printer.name().unwrap_or_default());
+///         }
+///     })
+///     .build()?;
+/// # Ok::<(), cpdb_rs::CpdbError>(())
+/// ```
+#[derive(Default)]
+pub struct FrontendBuilder {
+    instance_name: Option<String>,
+    observer: Option<Box<PrinterObserver>>,
+    ignore_last_saved_settings: bool,
+    temporary: bool,
+    event_buffer: Option<BufferPolicy>,
+}
+
+impl FrontendBuilder {
+    /// Creates a builder with default settings: generated instance name,
+    /// no observer, last-saved settings honoured.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the instance name reported by [`Frontend::instance_name`].
+    ///
+    /// Leave it unset to get a generated, unique one.
+    pub fn instance_name(mut self, name: impl Into<String>) -> Self {
+        self.instance_name = Some(name.into());
+        self
+    }
+
+    /// Installs a closure-based printer observer.
+    ///
+    /// Same semantics as [`Frontend::new_with_observer`].
+    pub fn observer<F>(mut self, observer: F) -> Self
+    where
+        F: FnMut(&Printer<'_>, PrinterUpdate) + Send + 'static,
+    {
+        self.observer = Some(Box::new(observer));
+        self
+    }
+
+    /// Skips the previously saved settings file
+    /// (`cpdbIgnoreLastSavedSettings`) on the built frontend.
+    ///
+    /// This is the opt-out from saved settings; merge them back without
+    /// overriding runtime values with [`Printer::apply_saved_settings`].
+    pub fn ignore_last_saved_settings(mut self, ignore: bool) -> Self {
+        self.ignore_last_saved_settings = ignore;
+        self
+    }
+
+    /// Marks the frontend as short-lived. See [`Frontend::new_temporary`].
+    ///
+    /// A temporary frontend always ignores the last-saved settings and,
+    /// unless one is set explicitly, gets an anonymous instance name.
+    pub fn temporary(mut self, temporary: bool) -> Self {
+        self.temporary = temporary;
+        self
+    }
+
+    /// Records every printer-update event into a buffer drained with
+    /// [`Frontend::drain_events`].
+    ///
+    /// `policy` bounds the buffer; see [`crate::discovery`] for the
+    /// tradeoffs. The buffer is fed by a listener, so it coexists with any
+    /// [`FrontendBuilder::observer`].
+    pub fn event_buffer(mut self, policy: BufferPolicy) -> Self {
+        self.event_buffer = Some(policy);
+        self
+    }
+
+    /// Builds the frontend.
+    pub fn build(self) -> Result<Frontend> {
+        let prefix = if self.temporary {
+            "cpdb-rs-tmp"
+        } else {
+            "cpdb-rs"
+        };
+        let instance_name = self
+            .instance_name
+            .unwrap_or_else(|| default_instance_name(prefix));
+        let has_listeners = self.observer.is_some() || self.event_buffer.is_some();
+        let cb: ffi::cpdb_printer_callback = if has_listeners {
+            Some(callbacks::printer_trampoline)
+        } else {
+            None
+        };
+        let mut frontend = Frontend::construct(cb, instance_name, self.temporary)?;
+        if self.ignore_last_saved_settings || self.temporary {
+            frontend.ignore_last_saved_settings();
+        }
+        if let Some(observer) = self.observer {
+            callbacks::register_printer_observer(frontend.raw.as_ptr(), observer);
+        }
+        if let Some(policy) = self.event_buffer {
+            let buffer = Arc::new(EventBuffer::new(policy));
+            let sink = Arc::clone(&buffer);
+            callbacks::add_printer_listener(
+                frontend.raw.as_ptr(),
+                Box::new(move |printer, update| {
+                    sink.push(DiscoveryEvent::from_printer(printer, update));
+                }),
+            );
+            frontend.events = Some(buffer);
+        }
+        Ok(frontend)
+    }
+}
+
+/// Snapshots the printer pointers stored in a frontend's table.
+///
+/// # Safety
+/// `frontend` must point at a live frontend object whose printer table is
+/// not mutated for the duration of the call.
+pub(crate) unsafe fn printer_table_ptrs(
+    frontend: *mut ffi::cpdb_frontend_obj_t,
+) -> Vec<*mut ffi::cpdb_printer_obj_t> {
+    // SAFETY: dereferencing the printer table field is sound; we only
+    // read borrowed pointers and never write through them.
+    let table = unsafe { (*frontend).printer } as *mut glib_sys::GHashTable;
+    if table.is_null() {
+        return Vec::new();
+    }
+
+    let mut ptrs = Vec::new();
+    // SAFETY: iterator is initialised on the stack and iterated
+    // synchronously; the table is not mutated during this loop.
+    unsafe {
+        let mut iter = MaybeUninit::<glib_sys::GHashTableIter>::uninit();
+        glib_sys::g_hash_table_iter_init(iter.as_mut_ptr(), table);
+        let mut iter = iter.assume_init();
+
+        let mut key: glib_sys::gpointer = std::ptr::null_mut();
+        let mut value: glib_sys::gpointer = std::ptr::null_mut();
+        while glib_sys::g_hash_table_iter_next(&mut iter, &mut key, &mut value) != 0 {
+            ptrs.push(value as *mut ffi::cpdb_printer_obj_t);
+        }
+    }
+    ptrs
+}
+
+/// Where cpdb-libs looks for installed backend service files, as
+/// resolved by the build script.
+const BACKEND_INFO_DIR: &str = env!("CPDB_BACKEND_INFO_DIR");
+
+/// The D-Bus service prefix of backend service file names.
+const BACKEND_SERVICE_PREFIX: &str = "org.openprinting.Backend.";
+
+/// Combines installed service file names with the active backend names
+/// into a sorted `(name, active)` list.
+fn merge_backend_status(installed: &[String], active: &[String]) -> Vec<(String, bool)> {
+    let mut status: Vec<(String, bool)> = installed
+        .iter()
+        .filter_map(|file| file.strip_prefix(BACKEND_SERVICE_PREFIX))
+        .filter(|name| !name.is_empty())
+        .map(|name| (name.to_owned(), active.iter().any(|a| a == name)))
+        .collect();
+    for name in active {
+        if !status.iter().any(|(n, _)| n == name) {
+            status.push((name.clone(), true));
+        }
+    }
+    status.sort();
+    status.dedup();
+    status
+}
+
+/// Generates a process-unique instance name of the form
+/// `<prefix>-<pid>-<n>`.
+fn default_instance_name(prefix: &str) -> String {
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+    let n = NEXT.fetch_add(1, Ordering::Relaxed);
+    format!("{prefix}-{}-{n}", std::process::id())
+}
+
 impl Drop for Frontend {
     fn drop(&mut self) {
+        // Release a callback blocked on a full event buffer so the
+        // unregistration below cannot wait on it forever.
+        if let Some(events) = &self.events {
+            events.close();
+        }
         // Unregister any observer FIRST so an in-flight callback from
         // cpdb-libs' D-Bus thread finds an empty slot and bails out
         // instead of touching a half-freed object.
         callbacks::unregister_printer_observer(self.raw.as_ptr());
+        printer::forget_runtime_settings(self.raw.as_ptr());
+        log::debug!("dropping frontend '{}'", self.instance_name);
         // SAFETY: we own the pointer.
         unsafe { ffi::cpdbDeleteFrontendObj(self.raw.as_ptr()) };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn disconnecting_an_unconnected_frontend_is_a_no_op() {
+        let frontend = Frontend::new_temporary().unwrap();
+        assert!(frontend.disconnect_from_dbus().is_ok());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn get_printer_propagates_nul_errors() {
+        let frontend = Frontend::new_temporary().unwrap();
+        for backend in [Some("CUPS"), None] {
+            assert!(matches!(
+                frontend.get_printer("bad\0id", backend),
+                Err(CpdbError::NulError(_))
+            ));
+        }
+        assert!(matches!(
+            frontend.get_printer("queue", Some("CU\0PS")),
+            Err(CpdbError::NulError(_))
+        ));
+    }
+
+    #[test]
+    fn backend_status_marks_active_backends() {
+        let installed = names(&[
+            "org.openprinting.Backend.FILE",
+            "org.openprinting.Backend.CUPS",
+            "README",
+        ]);
+        let active = names(&["CUPS", "LPRINT"]);
+        assert_eq!(
+            merge_backend_status(&installed, &active),
+            [
+                ("CUPS".to_string(), true),
+                ("FILE".to_string(), false),
+                ("LPRINT".to_string(), true),
+            ]
+        );
+    }
+}
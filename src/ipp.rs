@@ -0,0 +1,503 @@
+//! Typed views of IPP keyword values that cpdb-libs passes around as
+//! strings.
+//!
+//! Backends report printer state as a keyword (`"idle"`, `"printing"`,
+//! ...); raw IPP carries the same information as the `printer-state`
+//! enum (RFC 8011 §5.4.11). The types here bridge the two.
+
+use std::fmt;
+
+/// A printer's operational state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PrinterState {
+    /// Ready to accept and process jobs.
+    Idle,
+    /// Currently processing a job.
+    Processing,
+    /// Stopped; jobs are held until an operator intervenes.
+    Stopped,
+    /// Any state string or number not covered above.
+    Unknown,
+}
+
+impl PrinterState {
+    /// Parses a backend state string. Matching is ASCII case-insensitive;
+    /// `"printing"` (used by the CUPS backend) maps to
+    /// [`PrinterState::Processing`]. Unrecognised input yields
+    /// [`PrinterState::Unknown`].
+    pub fn parse(s: &str) -> Self {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("idle") {
+            Self::Idle
+        } else if s.eq_ignore_ascii_case("processing") || s.eq_ignore_ascii_case("printing") {
+            Self::Processing
+        } else if s.eq_ignore_ascii_case("stopped") {
+            Self::Stopped
+        } else {
+            Self::Unknown
+        }
+    }
+
+    /// Maps an IPP `printer-state` enum value (3 = idle, 4 = processing,
+    /// 5 = stopped). Any other value yields [`PrinterState::Unknown`].
+    pub fn from_ipp(value: i32) -> Self {
+        match value {
+            3 => Self::Idle,
+            4 => Self::Processing,
+            5 => Self::Stopped,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// The IPP `printer-state` enum value, or `None` for
+    /// [`PrinterState::Unknown`].
+    pub fn to_ipp(&self) -> Option<i32> {
+        match self {
+            Self::Idle => Some(3),
+            Self::Processing => Some(4),
+            Self::Stopped => Some(5),
+            Self::Unknown => None,
+        }
+    }
+
+    /// The IPP keyword for this state.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Idle => "idle",
+            Self::Processing => "processing",
+            Self::Stopped => "stopped",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+impl fmt::Display for PrinterState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The IPP `sides` keyword: simplex or one of the duplex bindings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sides {
+    /// Print on one side of the sheet (`one-sided`).
+    OneSided,
+    /// Duplex, flipped on the long edge (`two-sided-long-edge`) — portrait
+    /// book binding.
+    TwoSidedLongEdge,
+    /// Duplex, flipped on the short edge (`two-sided-short-edge`) —
+    /// calendar / landscape binding.
+    TwoSidedShortEdge,
+}
+
+impl Sides {
+    /// Parses an IPP `sides` keyword, or returns `None` for anything else.
+    pub fn from_keyword(keyword: &str) -> Option<Self> {
+        match keyword.trim() {
+            "one-sided" => Some(Self::OneSided),
+            "two-sided-long-edge" => Some(Self::TwoSidedLongEdge),
+            "two-sided-short-edge" => Some(Self::TwoSidedShortEdge),
+            _ => None,
+        }
+    }
+
+    /// The IPP keyword for this value.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::OneSided => "one-sided",
+            Self::TwoSidedLongEdge => "two-sided-long-edge",
+            Self::TwoSidedShortEdge => "two-sided-short-edge",
+        }
+    }
+
+    /// `true` for either duplex mode.
+    pub fn is_duplex(&self) -> bool {
+        !matches!(self, Self::OneSided)
+    }
+}
+
+impl fmt::Display for Sides {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The IPP `orientation-requested` value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    /// `portrait` (3).
+    Portrait,
+    /// `landscape` (4): content rotated 90° counter-clockwise.
+    Landscape,
+    /// `reverse-landscape` (5): content rotated 90° clockwise.
+    ReverseLandscape,
+    /// `reverse-portrait` (6): content rotated 180°.
+    ReversePortrait,
+}
+
+impl Orientation {
+    /// Parses either the keyword form (`"landscape"`) or the IPP enum
+    /// value (`"4"`) that backends report. Returns `None` for anything
+    /// else, including `none` (7).
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        match value {
+            "portrait" => Some(Self::Portrait),
+            "landscape" => Some(Self::Landscape),
+            "reverse-landscape" => Some(Self::ReverseLandscape),
+            "reverse-portrait" => Some(Self::ReversePortrait),
+            _ => value.parse().ok().and_then(Self::from_ipp),
+        }
+    }
+
+    /// Converts an IPP `orientation-requested` enum value.
+    pub fn from_ipp(value: i32) -> Option<Self> {
+        match value {
+            3 => Some(Self::Portrait),
+            4 => Some(Self::Landscape),
+            5 => Some(Self::ReverseLandscape),
+            6 => Some(Self::ReversePortrait),
+            _ => None,
+        }
+    }
+
+    /// The IPP enum value for this orientation.
+    pub fn to_ipp(&self) -> i32 {
+        match self {
+            Self::Portrait => 3,
+            Self::Landscape => 4,
+            Self::ReverseLandscape => 5,
+            Self::ReversePortrait => 6,
+        }
+    }
+
+    /// The keyword for this orientation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Portrait => "portrait",
+            Self::Landscape => "landscape",
+            Self::ReverseLandscape => "reverse-landscape",
+            Self::ReversePortrait => "reverse-portrait",
+        }
+    }
+}
+
+impl fmt::Display for Orientation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The IPP `presentation-direction-number-up` keyword: the order in which
+/// pages are placed on a sheet when printing several pages per side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Left to right, then top to bottom (`toright-tobottom`).
+    ToRightToBottom,
+    /// Top to bottom, then left to right (`tobottom-toright`).
+    ToBottomToRight,
+    /// Right to left, then top to bottom (`toleft-tobottom`).
+    ToLeftToBottom,
+    /// Top to bottom, then right to left (`tobottom-toleft`).
+    ToBottomToLeft,
+    /// Left to right, then bottom to top (`toright-totop`).
+    ToRightToTop,
+    /// Bottom to top, then left to right (`totop-toright`).
+    ToTopToRight,
+    /// Right to left, then bottom to top (`toleft-totop`).
+    ToLeftToTop,
+    /// Bottom to top, then right to left (`totop-toleft`).
+    ToTopToLeft,
+}
+
+impl Direction {
+    const ALL: [Self; 8] = [
+        Self::ToRightToBottom,
+        Self::ToBottomToRight,
+        Self::ToLeftToBottom,
+        Self::ToBottomToLeft,
+        Self::ToRightToTop,
+        Self::ToTopToRight,
+        Self::ToLeftToTop,
+        Self::ToTopToLeft,
+    ];
+
+    /// Parses an IPP direction keyword, or returns `None` for anything
+    /// else.
+    pub fn from_keyword(keyword: &str) -> Option<Self> {
+        let keyword = keyword.trim();
+        Self::ALL.into_iter().find(|d| d.as_str() == keyword)
+    }
+
+    /// The IPP keyword for this direction.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ToRightToBottom => "toright-tobottom",
+            Self::ToBottomToRight => "tobottom-toright",
+            Self::ToLeftToBottom => "toleft-tobottom",
+            Self::ToBottomToLeft => "tobottom-toleft",
+            Self::ToRightToTop => "toright-totop",
+            Self::ToTopToRight => "totop-toright",
+            Self::ToLeftToTop => "toleft-totop",
+            Self::ToTopToLeft => "totop-toleft",
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The kind of finishing applied by a [`FinishingsCol`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FinishingType {
+    /// Staples (`staple-*`).
+    Staple,
+    /// Punched holes (`punch-*`).
+    Punch,
+}
+
+impl FinishingType {
+    /// The IPP keyword prefix for this finishing.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Staple => "staple",
+            Self::Punch => "punch",
+        }
+    }
+
+    fn from_keyword(keyword: &str) -> Option<Self> {
+        match keyword {
+            "staple" => Some(Self::Staple),
+            "punch" => Some(Self::Punch),
+            _ => None,
+        }
+    }
+}
+
+/// Where on the sheet a finishing is applied (PWG 5100.1 reference
+/// edges and corners).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FinishingPosition {
+    /// One, in the top-left corner.
+    TopLeft,
+    /// One, in the bottom-left corner.
+    BottomLeft,
+    /// One, in the top-right corner.
+    TopRight,
+    /// One, in the bottom-right corner.
+    BottomRight,
+    /// Two, along the left edge.
+    DualLeft,
+    /// Two, along the top edge.
+    DualTop,
+    /// Two, along the right edge.
+    DualRight,
+    /// Two, along the bottom edge.
+    DualBottom,
+    /// Three, along the left edge.
+    TripleLeft,
+    /// Three, along the top edge.
+    TripleTop,
+    /// Three, along the right edge.
+    TripleRight,
+    /// Three, along the bottom edge.
+    TripleBottom,
+}
+
+impl FinishingPosition {
+    const ALL: [Self; 12] = [
+        Self::TopLeft,
+        Self::BottomLeft,
+        Self::TopRight,
+        Self::BottomRight,
+        Self::DualLeft,
+        Self::DualTop,
+        Self::DualRight,
+        Self::DualBottom,
+        Self::TripleLeft,
+        Self::TripleTop,
+        Self::TripleRight,
+        Self::TripleBottom,
+    ];
+
+    /// The position part of the IPP keyword, e.g. `"top-left"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TopLeft => "top-left",
+            Self::BottomLeft => "bottom-left",
+            Self::TopRight => "top-right",
+            Self::BottomRight => "bottom-right",
+            Self::DualLeft => "dual-left",
+            Self::DualTop => "dual-top",
+            Self::DualRight => "dual-right",
+            Self::DualBottom => "dual-bottom",
+            Self::TripleLeft => "triple-left",
+            Self::TripleTop => "triple-top",
+            Self::TripleRight => "triple-right",
+            Self::TripleBottom => "triple-bottom",
+        }
+    }
+
+    fn from_keyword(keyword: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.as_str() == keyword)
+    }
+}
+
+/// A positioned finishing, as carried by the IPP `finishings-col`
+/// collection.
+///
+/// cpdb-libs passes option values as strings, so the collection is sent
+/// in its text form, `{finishing-template=staple-top-left}`. The same
+/// template keyword doubles as the plain `finishings` value for printers
+/// without collection support.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FinishingsCol {
+    /// What to apply.
+    pub finishing: FinishingType,
+    /// Where to apply it.
+    pub position: FinishingPosition,
+}
+
+impl FinishingsCol {
+    /// Creates a finishing at `position`.
+    pub fn new(finishing: FinishingType, position: FinishingPosition) -> Self {
+        Self {
+            finishing,
+            position,
+        }
+    }
+
+    /// The `finishing-template` keyword, e.g. `"staple-top-left"`.
+    pub fn template(&self) -> String {
+        format!("{}-{}", self.finishing.as_str(), self.position.as_str())
+    }
+
+    /// Parses a `finishing-template` / `finishings` keyword, or returns
+    /// `None` for keywords without a position (`"staple"`, `"none"`, ...).
+    pub fn from_template(keyword: &str) -> Option<Self> {
+        let (finishing, position) = keyword.trim().split_once('-')?;
+        Some(Self::new(
+            FinishingType::from_keyword(finishing)?,
+            FinishingPosition::from_keyword(position)?,
+        ))
+    }
+
+    /// The `finishings-col` value in IPP collection text form.
+    pub fn to_collection_string(&self) -> String {
+        format!("{{finishing-template={}}}", self.template())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_backend_state_strings() {
+        assert_eq!(PrinterState::parse("idle"), PrinterState::Idle);
+        assert_eq!(PrinterState::parse("Printing"), PrinterState::Processing);
+        assert_eq!(PrinterState::parse("processing"), PrinterState::Processing);
+        assert_eq!(PrinterState::parse(" stopped "), PrinterState::Stopped);
+        assert_eq!(PrinterState::parse("NA"), PrinterState::Unknown);
+    }
+
+    #[test]
+    fn ipp_numeric_round_trip() {
+        for (n, state) in [
+            (3, PrinterState::Idle),
+            (4, PrinterState::Processing),
+            (5, PrinterState::Stopped),
+        ] {
+            assert_eq!(PrinterState::from_ipp(n), state);
+            assert_eq!(state.to_ipp(), Some(n));
+        }
+    }
+
+    #[test]
+    fn unknown_ipp_values_fall_back() {
+        assert_eq!(PrinterState::from_ipp(0), PrinterState::Unknown);
+        assert_eq!(PrinterState::from_ipp(6), PrinterState::Unknown);
+        assert_eq!(PrinterState::Unknown.to_ipp(), None);
+    }
+
+    #[test]
+    fn sides_keywords_round_trip() {
+        for sides in [
+            Sides::OneSided,
+            Sides::TwoSidedLongEdge,
+            Sides::TwoSidedShortEdge,
+        ] {
+            assert_eq!(Sides::from_keyword(sides.as_str()), Some(sides));
+        }
+        assert_eq!(Sides::from_keyword("duplex"), None);
+        assert!(!Sides::OneSided.is_duplex());
+        assert!(Sides::TwoSidedShortEdge.is_duplex());
+    }
+
+    #[test]
+    fn orientation_accepts_keywords_and_enum_values() {
+        assert_eq!(
+            Orientation::parse("landscape"),
+            Some(Orientation::Landscape)
+        );
+        assert_eq!(
+            Orientation::parse(" 6 "),
+            Some(Orientation::ReversePortrait)
+        );
+        assert_eq!(Orientation::parse("7"), None);
+        assert_eq!(Orientation::parse("none"), None);
+        for o in [
+            Orientation::Portrait,
+            Orientation::Landscape,
+            Orientation::ReverseLandscape,
+            Orientation::ReversePortrait,
+        ] {
+            assert_eq!(Orientation::from_ipp(o.to_ipp()), Some(o));
+            assert_eq!(Orientation::parse(o.as_str()), Some(o));
+        }
+    }
+
+    #[test]
+    fn direction_keywords_round_trip() {
+        for direction in Direction::ALL {
+            assert_eq!(Direction::from_keyword(direction.as_str()), Some(direction));
+        }
+        assert_eq!(
+            Direction::from_keyword("toleft-tobottom"),
+            Some(Direction::ToLeftToBottom)
+        );
+        assert_eq!(Direction::from_keyword("left-to-right"), None);
+    }
+
+    #[test]
+    fn finishings_template_round_trip() {
+        let col = FinishingsCol::new(FinishingType::Staple, FinishingPosition::TopLeft);
+        assert_eq!(col.template(), "staple-top-left");
+        assert_eq!(
+            col.to_collection_string(),
+            "{finishing-template=staple-top-left}"
+        );
+        assert_eq!(FinishingsCol::from_template("staple-top-left"), Some(col));
+        assert_eq!(
+            FinishingsCol::from_template("punch-dual-left"),
+            Some(FinishingsCol::new(
+                FinishingType::Punch,
+                FinishingPosition::DualLeft
+            ))
+        );
+        assert_eq!(FinishingsCol::from_template("staple"), None);
+        assert_eq!(FinishingsCol::from_template("bind-left"), None);
+    }
+
+    #[test]
+    fn display_matches_keyword() {
+        assert_eq!(PrinterState::Processing.to_string(), "processing");
+        assert_eq!(
+            PrinterState::parse(&PrinterState::Stopped.to_string()),
+            PrinterState::Stopped
+        );
+    }
+}
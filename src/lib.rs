@@ -12,23 +12,39 @@
 
 pub mod callbacks;
 pub mod common;
+pub mod discovery;
 pub mod error;
 pub mod ffi;
 pub mod frontend;
+pub mod ipp;
 pub mod options;
 pub mod printer;
 pub mod settings;
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub mod snapshot;
+pub mod translation_cache;
 pub mod util;
+pub mod variant;
 
-pub use callbacks::PrinterUpdate;
+pub use callbacks::{PrinterEvent, PrinterUpdate};
 pub use common::{
-    absolute_path, concat_path, concat_sep, init, option_group, system_config_dir, user_config_dir,
-    version,
+    SemVer, absolute_path, concat_path, concat_sep, init, option_group, parsed_version,
+    system_config_dir, user_config_dir, version,
 };
+pub use discovery::{BufferPolicy, CancellationHandle, DiscoveryEvent, DiscoveryWarning};
 pub use error::{CpdbError, Result};
-pub use frontend::Frontend;
+pub use frontend::{Frontend, FrontendBuilder};
+pub use ipp::{
+    Direction, FinishingPosition, FinishingType, FinishingsCol, Orientation, PrinterState, Sides,
+};
 pub use options::{OptionInfo, OptionsCollection};
 pub use printer::{
-    Margin, Margins, MediaSize, PrintFdHandle, PrintSocketHandle, Printer, TranslationMap,
+    Margin, Margins, MediaInfo, MediaSize, PrintFdHandle, PrintResult, PrintSocketHandle, Printer,
+    RAW_DOCUMENT_FORMAT, TranslationMap,
 };
 pub use settings::{Media, Options, Settings};
+#[cfg(feature = "serde")]
+pub use snapshot::{FleetDiff, FleetSnapshot, PrinterChange, PrinterSnapshot};
+pub use translation_cache::TranslationCache;
+pub use variant::GVariantRef;
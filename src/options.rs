@@ -11,6 +11,7 @@ use std::ptr::NonNull;
 
 /// A single printer option with its supported choices.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OptionInfo {
     /// The option name, e.g. `"copies"` or `"sides"`.
     pub name: String,
@@ -22,6 +23,13 @@ pub struct OptionInfo {
     pub supported_values: Vec<String>,
 }
 
+impl OptionInfo {
+    /// `true` when `value` is one of [`OptionInfo::supported_values`].
+    pub fn supports(&self, value: &str) -> bool {
+        self.supported_values.iter().any(|v| v == value)
+    }
+}
+
 /// An owned snapshot of every option in a `cpdb_options_t`.
 ///
 /// Built by iterating `cpdb_options_t.table` once and copying every field
@@ -100,13 +108,25 @@ impl OptionsCollection {
     }
 }
 
+/// Renders one option as `name: current (default) [supported, ...]`.
+///
+/// The bracketed list is omitted when the option reports no supported
+/// values.
+pub(crate) fn format_option_line(info: &OptionInfo, current: &str) -> String {
+    let mut line = format!("{}: {current} ({})", info.name, info.default_value);
+    if !info.supported_values.is_empty() {
+        line.push_str(&format!(" [{}]", info.supported_values.join(", ")));
+    }
+    line
+}
+
 /// Copies one `cpdb_option_t` into an owned [`OptionInfo`].
 ///
 /// # Safety
 /// `opt` must be a valid pointer into a live `cpdb_option_t` whose string
 /// fields are NUL-terminated and whose `supported_values` array (if any)
 /// has at least `num_supported` valid entries.
-unsafe fn option_info_from_raw(opt: *mut ffi::cpdb_option_t) -> OptionInfo {
+pub(crate) unsafe fn option_info_from_raw(opt: *mut ffi::cpdb_option_t) -> OptionInfo {
     let name = unsafe { util::cstr_to_string((*opt).option_name) }.unwrap_or_default();
     let default_value = unsafe { util::cstr_to_string((*opt).default_value) }.unwrap_or_default();
     let group = unsafe { util::cstr_to_string((*opt).group_name) }.unwrap_or_default();
@@ -152,6 +172,46 @@ mod tests {
         assert!(result.is_empty());
     }
 
+    #[test]
+    fn option_info_decodes_every_field() {
+        use std::ffi::CString;
+
+        let name = CString::new("sides").unwrap();
+        let group = CString::new("General").unwrap();
+        let default = CString::new("one-sided").unwrap();
+        let values = [
+            CString::new("one-sided").unwrap(),
+            CString::new("two-sided-long-edge").unwrap(),
+        ];
+        let mut ptrs = [
+            values[0].as_ptr() as *mut libc::c_char,
+            std::ptr::null_mut(),
+            values[1].as_ptr() as *mut libc::c_char,
+        ];
+        let mut opt = ffi::cpdb_option_t {
+            option_name: name.as_ptr() as *mut libc::c_char,
+            group_name: group.as_ptr() as *mut libc::c_char,
+            num_supported: ptrs.len() as i32,
+            supported_values: ptrs.as_mut_ptr(),
+            default_value: default.as_ptr() as *mut libc::c_char,
+        };
+
+        // SAFETY: every pointer refers to a live local; the array holds
+        // `num_supported` entries. Null entries are skipped.
+        let info = unsafe { option_info_from_raw(&mut opt) };
+        assert_eq!(info.name, "sides");
+        assert_eq!(info.group, "General");
+        assert_eq!(info.default_value, "one-sided");
+        assert_eq!(info.supported_values, ["one-sided", "two-sided-long-edge"]);
+
+        opt.supported_values = std::ptr::null_mut();
+        opt.default_value = std::ptr::null_mut();
+        // SAFETY: as above; a null array and null default decode as empty.
+        let info = unsafe { option_info_from_raw(&mut opt) };
+        assert!(info.supported_values.is_empty());
+        assert_eq!(info.default_value, "");
+    }
+
     #[test]
     fn empty_collection_helpers() {
         let col = OptionsCollection::default();
@@ -207,4 +267,26 @@ mod tests {
         assert_eq!(col.len(), 2);
         assert_eq!(col.iter().count(), 2);
     }
+
+    #[test]
+    fn option_line_formatting() {
+        let info = OptionInfo {
+            name: "sides".into(),
+            default_value: "one-sided".into(),
+            group: String::new(),
+            supported_values: vec!["one-sided".into(), "two-sided-long-edge".into()],
+        };
+        assert_eq!(
+            format_option_line(&info, "two-sided-long-edge"),
+            "sides: two-sided-long-edge (one-sided) [one-sided, two-sided-long-edge]"
+        );
+
+        let bare = OptionInfo {
+            name: "copies".into(),
+            default_value: "1".into(),
+            group: String::new(),
+            supported_values: Vec::new(),
+        };
+        assert_eq!(format_option_line(&bare, "3"), "copies: 3 (1)");
+    }
 }
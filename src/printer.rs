@@ -5,7 +5,8 @@
 //! Printers come in two flavours:
 //!
 //! - **Borrowed** — returned by [`crate::Frontend::find_printer`],
-//!   [`crate::Frontend::get_printer`], [`crate::Frontend::get_printers`],
+//!   [`crate::Frontend::get_printer`], [`crate::Frontend::get_printer_by_name`],
+//!   [`crate::Frontend::get_printers`],
 //!   and the default-printer accessors. The underlying C object is owned by
 //!   the frontend's hash table; the Rust binding carries the frontend's
 //!   lifetime so the borrow checker enforces that the printer cannot outlive
@@ -14,6 +15,32 @@
 //!   allocated independently; Rust frees it via `cpdbDeletePrinterObj` on
 //!   drop. Owned printers have a `'static` lifetime.
 //!
+//! `Printer` does not implement `Clone`. [`Printer::view`] gives a second,
+//! non-owning handle that borrows from the original, so an owned printer
+//! is freed exactly once however many views were taken.
+//!
+//! # Option precedence
+//!
+//! The value a job uses for an option resolves, highest first:
+//!
+//! 1. **Runtime** — [`Printer::add_setting`] and the setters built on it,
+//!    or the options passed to [`Printer::submit_job`].
+//! 2. **Saved** — the settings cpdb-libs persisted from earlier jobs and
+//!    copies into every printer object it adds.
+//! 3. **Default** — the backend's default for the option.
+//!
+//! [`Printer::get_current`] reports the resolved value. cpdb-libs itself
+//! cannot tell a runtime setting from a saved one, so the binding records
+//! runtime settings per printer — by frontend, backend and id, so they
+//! outlive individual handles and the fresh object cpdb-libs creates when
+//! a printer is rediscovered — and writes them back over anything merged
+//! in since: when the printer is reported added, before
+//! [`Printer::get_current`], and before every submission.
+//! [`Printer::clear_setting`] drops a runtime value so the saved one
+//! applies again. Build the frontend with
+//! [`crate::FrontendBuilder::ignore_last_saved_settings`] to opt out of
+//! saved settings.
+//!
 //! `Printer` deliberately does not implement [`Send`] or [`Sync`]. Most
 //! cpdb-libs methods on a printer object mutate shared state (the printer's
 //! settings table is shared with the frontend), and the C library does not
@@ -24,15 +51,29 @@ use crate::callbacks::{self, AcquireCompletion};
 use crate::error::{CpdbError, Result};
 use crate::ffi;
 use crate::frontend::Frontend;
-use crate::options::OptionsCollection;
+use crate::ipp::{Direction, FinishingType, FinishingsCol, Orientation, PrinterState, Sides};
+use crate::options::{self, OptionInfo, OptionsCollection};
+use crate::settings::Settings;
+use crate::translation_cache::TranslationCache;
 use crate::util;
+use crate::variant::GVariantRef;
 use libc::c_char;
 use std::collections::HashMap;
 use std::ffi::{CStr, CString};
+use std::fs::File;
+use std::io::{self, Read};
 use std::marker::PhantomData;
 use std::mem::MaybeUninit;
-use std::os::fd::{FromRawFd, OwnedFd};
+use std::os::fd::{AsFd, BorrowedFd, FromRawFd, OwnedFd};
+use std::os::unix::net::UnixStream;
+use std::path::Path;
 use std::ptr::NonNull;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::time::Duration;
+
+/// The CUPS document format that disables all filtering, for
+/// [`Printer::print_raw`].
+pub const RAW_DOCUMENT_FORMAT: &str = "application/vnd.cups-raw";
 
 /// Page margins in hundredths of a millimetre.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -63,6 +104,30 @@ pub struct MediaSize {
     pub length: i32,
 }
 
+/// One media size the printer advertises, from [`Printer::list_media`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaInfo {
+    /// The PWG media name, e.g. `iso_a4_210x297mm`.
+    pub name: String,
+    /// Width in hundredths of a millimetre.
+    pub width: i32,
+    /// Length in hundredths of a millimetre.
+    pub length: i32,
+    /// Every margin set the backend reports, as
+    /// [`Printer::get_media_margins`] returns them.
+    pub margins: Vec<Margin>,
+}
+
+impl MediaInfo {
+    /// The media's dimensions.
+    pub fn size(&self) -> MediaSize {
+        MediaSize {
+            width: self.width,
+            length: self.length,
+        }
+    }
+}
+
 /// Handle returned by [`Printer::print_fd`].
 ///
 /// The caller writes the document data to [`PrintFdHandle::fd`] and then
@@ -86,6 +151,22 @@ pub struct PrintSocketHandle {
     pub job_id: String,
 }
 
+/// Outcome of a print submission with byte accounting.
+///
+/// Returned by the streaming submit paths ([`Printer::print_bytes`],
+/// [`Printer::print_reader`], [`Printer::print_reader_via_socket`]) and by
+/// [`Printer::print_file_accounted`].
+///
+/// The job ID cannot be used to cancel or query the job through this
+/// crate; see [`Printer::submit_job`] for why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrintResult {
+    /// The backend-assigned job ID, or an empty string when not provided.
+    pub job_id: String,
+    /// Number of document bytes handed to the backend.
+    pub bytes_sent: u64,
+}
+
 /// An owned snapshot of a printer's translation table.
 ///
 /// Built by walking `cpdb_printer_obj.translations` once and copying every
@@ -124,6 +205,11 @@ impl TranslationMap {
 pub struct Printer<'frontend> {
     raw: NonNull<ffi::cpdb_printer_obj_t>,
     owned: bool,
+    // The frontend whose table holds a borrowed printer, when known; used
+    // by `is_valid`.
+    frontend: Option<NonNull<ffi::cpdb_frontend_obj_t>>,
+    // Set by `require_account_id`; checked before every submission.
+    account_required: bool,
     // Borrowed printers borrow from a `Frontend`; using a non-`Send`/`Sync`
     // marker also keeps owned printers off other threads, which matches
     // cpdb-libs' lack of internal locking.
@@ -142,10 +228,41 @@ impl<'frontend> Printer<'frontend> {
         Ok(Self {
             raw,
             owned: false,
+            frontend: None,
+            account_required: false,
             _marker: PhantomData,
         })
     }
 
+    /// Like [`Printer::from_raw_borrowed`], additionally remembering the
+    /// frontend whose table holds the printer so [`Printer::is_valid`] can
+    /// check membership.
+    pub(crate) fn from_frontend_table(
+        raw: *mut ffi::cpdb_printer_obj_t,
+        frontend: *mut ffi::cpdb_frontend_obj_t,
+    ) -> Result<Self> {
+        let mut printer = Self::from_raw_borrowed(raw)?;
+        printer.frontend = NonNull::new(frontend);
+        Ok(printer)
+    }
+
+    /// A second, non-owning handle to the same printer object.
+    ///
+    /// `Printer` is deliberately not `Clone`: copying an owned printer's
+    /// pointer would free it twice. A view never frees the object, and its
+    /// lifetime is tied to `self`, so the borrow checker keeps it from
+    /// outliving an owned printer. Settings changed through a view are
+    /// visible through `self`, since both refer to the same C object.
+    pub fn view(&self) -> Printer<'_> {
+        Printer {
+            raw: self.raw,
+            owned: false,
+            frontend: self.frontend,
+            account_required: self.account_required,
+            _marker: PhantomData,
+        }
+    }
+
     /// Wraps a printer object that the binding will free on drop.
     ///
     /// Used for printers loaded from a pickle file.
@@ -154,6 +271,8 @@ impl<'frontend> Printer<'frontend> {
         Ok(Self {
             raw,
             owned: true,
+            frontend: None,
+            account_required: false,
             _marker: PhantomData,
         })
     }
@@ -164,6 +283,31 @@ impl<'frontend> Printer<'frontend> {
         self.raw.as_ptr()
     }
 
+    /// `true` while the handle still refers to a live printer object.
+    ///
+    /// A borrowed printer is valid while its frontend's printer table
+    /// still contains it. cpdb-libs removes a printer from the table and
+    /// frees it right after delivering [`PrinterUpdate::Removed`] for it,
+    /// so a handle cached across that event reports `false` here and must
+    /// not be used. The check compares pointers only and never reads the
+    /// possibly freed object; if the allocator hands the same address to a
+    /// newly added printer, the stale handle reads as valid again, so
+    /// compare [`Printer::id`] as well when that matters.
+    ///
+    /// Owned printers, and borrowed printers handed to completion
+    /// callbacks, are not tied to a table and always report `true`.
+    ///
+    /// [`PrinterUpdate::Removed`]: crate::PrinterUpdate::Removed
+    pub fn is_valid(&self) -> bool {
+        match self.frontend {
+            // SAFETY: the printer borrows from the frontend, which
+            // therefore outlives it; the table walk only compares pointers.
+            Some(frontend) => unsafe { crate::frontend::printer_table_ptrs(frontend.as_ptr()) }
+                .contains(&self.raw.as_ptr()),
+            None => true,
+        }
+    }
+
     /// `true` when this binding will free the underlying C object on drop.
     #[doc(hidden)]
     pub(crate) fn is_owned(&self) -> bool {
@@ -209,6 +353,31 @@ impl<'frontend> Printer<'frontend> {
         self.read_str_field(|p| unsafe { (*p).state })
     }
 
+    /// Checks that the identifying fields (id, name, backend name) are
+    /// present and valid UTF-8, returning a description of the first
+    /// problem found.
+    pub(crate) fn check_identity(&self) -> std::result::Result<(), String> {
+        let raw = self.raw.as_ptr();
+        // SAFETY: plain field reads of a live printer object.
+        let fields = unsafe {
+            [
+                ("id", (*raw).id),
+                ("name", (*raw).name),
+                ("backend name", (*raw).backend_name),
+            ]
+        };
+        for (field, ptr) in fields {
+            if ptr.is_null() {
+                return Err(format!("missing {field}"));
+            }
+            // SAFETY: non-null, NUL-terminated string owned by the printer.
+            if unsafe { CStr::from_ptr(ptr) }.to_str().is_err() {
+                return Err(format!("{field} is not valid UTF-8"));
+            }
+        }
+        Ok(())
+    }
+
     /// Reads an optional NUL-terminated string field from the printer struct.
     fn read_str_field<F>(&self, accessor: F) -> Result<String>
     where
@@ -238,21 +407,66 @@ impl<'frontend> Printer<'frontend> {
         }
     }
 
+    /// Queries the current state from the backend as a [`PrinterState`].
+    pub fn state(&self) -> Result<PrinterState> {
+        self.get_updated_state().map(|s| PrinterState::parse(&s))
+    }
+
     /// `true` when the printer is accepting jobs.
+    ///
+    /// Asks the backend over D-Bus on every call (and refreshes the value
+    /// returned by [`Printer::is_accepting_jobs_cached`]). Prefer the
+    /// cached read in loops driven by printer-update events.
     pub fn is_accepting_jobs(&self) -> Result<bool> {
         // SAFETY: pointer is non-null.
         Ok(unsafe { ffi::cpdbIsAcceptingJobs(self.raw.as_ptr()) } != 0)
     }
 
+    /// The last known accepting-jobs flag, without contacting the backend.
+    ///
+    /// cpdb-libs updates this value when the printer is discovered, on
+    /// every [`PrinterUpdate::StateChanged`] signal, and whenever
+    /// [`Printer::is_accepting_jobs`] is called. Between those points it
+    /// may be stale: a backend that changes state without emitting a
+    /// signal is not reflected until the next fresh read.
+    ///
+    /// [`PrinterUpdate::StateChanged`]: crate::PrinterUpdate::StateChanged
+    pub fn is_accepting_jobs_cached(&self) -> bool {
+        // SAFETY: pointer is non-null; `accepting_jobs` is a plain field
+        // written by cpdb-libs' signal handlers.
+        unsafe { (*self.raw.as_ptr()).accepting_jobs != 0 }
+    }
+
+    /// How long the printer has been up, from the IPP `printer-up-time`
+    /// attribute (seconds).
+    ///
+    /// Returns `None` when the backend does not report the attribute or
+    /// reports something other than a non-negative integer. The value is
+    /// as fresh as the option table; call [`Printer::acquire_details`] to
+    /// refresh it.
+    pub fn up_time(&self) -> Result<Option<Duration>> {
+        Ok(self
+            .get_option_info("printer-up-time")?
+            .and_then(|info| parse_up_time(&info.default_value)))
+    }
+
     // ─── Defaults ────────────────────────────────────────────────────────────
 
     /// Marks this printer as the user's default. Returns `true` on success.
+    ///
+    /// Prefer [`Frontend::set_user_default`](crate::Frontend::set_user_default),
+    /// which checks the printer is still live and reports failure as an error.
+    #[deprecated(note = "use `Frontend::set_user_default`")]
     pub fn set_user_default(&self) -> Result<bool> {
         // SAFETY: pointer is non-null.
         Ok(unsafe { ffi::cpdbSetUserDefaultPrinter(self.raw.as_ptr()) } != 0)
     }
 
     /// Marks this printer as the system-wide default. Returns `true` on success.
+    ///
+    /// Prefer [`Frontend::set_system_default`](crate::Frontend::set_system_default),
+    /// which checks the printer is still live and reports failure as an error.
+    #[deprecated(note = "use `Frontend::set_system_default`")]
     pub fn set_system_default(&self) -> Result<bool> {
         // SAFETY: pointer is non-null.
         Ok(unsafe { ffi::cpdbSetSystemDefaultPrinter(self.raw.as_ptr()) } != 0)
@@ -263,8 +477,10 @@ impl<'frontend> Printer<'frontend> {
     /// Submits a file as a print job with no extra options.
     ///
     /// Returns the backend-assigned job ID string.
-    pub fn print_file(&self, file_path: &str) -> Result<String> {
-        let c_path = CString::new(file_path)?;
+    pub fn print_file(&self, file_path: impl AsRef<Path>) -> Result<String> {
+        let c_path = util::path_to_cstring(file_path.as_ref())?;
+        self.check_account_id(&[])?;
+        self.reapply_runtime_settings()?;
         // SAFETY: cpdbPrintFile returns a `g_strdup`'d job ID we own.
         unsafe {
             let id = ffi::cpdbPrintFile(self.raw.as_ptr(), c_path.as_ptr());
@@ -281,6 +497,8 @@ impl<'frontend> Printer<'frontend> {
     /// returned.
     pub fn print_fd(&self, title: &str) -> Result<PrintFdHandle> {
         let c_title = CString::new(title)?;
+        self.check_account_id(&[])?;
+        self.reapply_runtime_settings()?;
         let mut jobid_ptr: *mut c_char = std::ptr::null_mut();
         let mut socket_ptr: *mut c_char = std::ptr::null_mut();
         // SAFETY: pointers are non-null; output params receive
@@ -332,6 +550,8 @@ impl<'frontend> Printer<'frontend> {
     /// the job data through, plus the backend-assigned job ID.
     pub fn print_socket(&self, title: &str) -> Result<PrintSocketHandle> {
         let c_title = CString::new(title)?;
+        self.check_account_id(&[])?;
+        self.reapply_runtime_settings()?;
         let mut jobid_ptr: *mut c_char = std::ptr::null_mut();
         // SAFETY: cpdb returns a `g_strdup`'d socket path we own.
         let socket_ptr =
@@ -356,6 +576,131 @@ impl<'frontend> Printer<'frontend> {
         })
     }
 
+    /// Prints the crate's built-in test page and returns the job ID.
+    ///
+    /// The page is a one-page PostScript document (`assets/test-page.ps`,
+    /// MIT-licensed with the crate): a border inset from the imageable
+    /// area, a title, a ten-step grey ramp, and corner crosshairs. It is
+    /// laid out from the device's clipping path, so it fits A4 and Letter.
+    ///
+    /// Fails with [`CpdbError::PrinterError`] if the printer reports that
+    /// it is not accepting jobs.
+    pub fn print_test_page(&self) -> Result<String> {
+        if !self.is_accepting_jobs()? {
+            return Err(CpdbError::PrinterError(format!(
+                "printer '{}' is not accepting jobs",
+                self.name().unwrap_or_default()
+            )));
+        }
+        self.print_bytes(TEST_PAGE_PS, "cpdb-rs test page")
+            .map(|result| result.job_id)
+    }
+
+    /// Submits an in-memory document over `cpdbPrintFD`.
+    ///
+    /// `bytes_sent` in the result equals `data.len()` once the write
+    /// completes.
+    pub fn print_bytes(&self, data: &[u8], title: &str) -> Result<PrintResult> {
+        self.print_reader(data, title)
+    }
+
+    /// Submits already-rendered data (PCL, raster, ...) with an explicit
+    /// MIME `format` and returns the job ID.
+    ///
+    /// `document-format` is set to `format` for this job only, as with
+    /// [`Printer::submit_with`], so the backend does not auto-detect and
+    /// convert the data. To bypass CUPS filtering entirely, pass
+    /// [`RAW_DOCUMENT_FORMAT`]. If the printer lists its document formats
+    /// and `format` is not among them, or it lists none, a warning is
+    /// logged and the job is still submitted: the backend may convert or
+    /// reject the data.
+    ///
+    /// Fails with [`CpdbError::OptionError`] when `format` is not a
+    /// `type/subtype` MIME type.
+    pub fn print_raw(&self, data: &[u8], format: &str, title: &str) -> Result<String> {
+        if let Some(warning) =
+            raw_format_warning(format, self.get_option_info("document-format")?.as_ref())?
+        {
+            log::warn!(
+                "print_raw on '{}': {warning}",
+                self.name().unwrap_or_default()
+            );
+        }
+        let overlay = SettingsOverlay::apply(
+            self,
+            vec![("document-format".to_owned(), format.to_owned())],
+        )?;
+        let result = self.print_bytes(data, title);
+        drop(overlay);
+        result.map(|result| result.job_id)
+    }
+
+    /// Streams `reader` to the backend over `cpdbPrintFD`, counting the
+    /// bytes written.
+    ///
+    /// The job file descriptor is closed before this returns, which tells
+    /// the backend the document is complete.
+    pub fn print_reader<R: Read>(&self, mut reader: R, title: &str) -> Result<PrintResult> {
+        let PrintFdHandle { fd, job_id, .. } = self.print_fd(title)?;
+        let mut sink = File::from(fd);
+        let bytes_sent = io::copy(&mut reader, &mut sink)?;
+        Ok(PrintResult { job_id, bytes_sent })
+    }
+
+    /// Streams `reader` to the backend over the Unix-domain socket returned
+    /// by `cpdbPrintSocket`, counting the bytes written.
+    pub fn print_reader_via_socket<R: Read>(
+        &self,
+        mut reader: R,
+        title: &str,
+    ) -> Result<PrintResult> {
+        let PrintSocketHandle {
+            socket_path,
+            job_id,
+        } = self.print_socket(title)?;
+        let mut sink = UnixStream::connect(&socket_path)?;
+        let bytes_sent = io::copy(&mut reader, &mut sink)?;
+        Ok(PrintResult { job_id, bytes_sent })
+    }
+
+    /// Prints everything readable from `source`, e.g. the read end of a
+    /// pipe, streaming it to the backend over `cpdbPrintFD`. Returns the
+    /// job ID.
+    ///
+    /// Ownership of the descriptor is not transferred: the data is read
+    /// through a duplicate, and `source` stays open for the caller to
+    /// close. Reading stops at end of file, so close the write end of a
+    /// pipe once the document is written. Any open `File`, `UnixStream`
+    /// or `OwnedFd` works; taking [`AsFd`] rather than a raw descriptor
+    /// rules out negative or already closed ones at compile time.
+    pub fn print_from_fd(&self, source: impl AsFd, title: &str) -> Result<String> {
+        let reader = duplicate_reader(source.as_fd())?;
+        self.print_reader(reader, title).map(|result| result.job_id)
+    }
+
+    /// Like [`Printer::print_from_fd`], but hands the data to the backend
+    /// over the Unix-domain socket from `cpdbPrintSocket`.
+    ///
+    /// `source` is typically a connected `UnixStream` and, again, is not
+    /// closed.
+    pub fn print_from_socket(&self, source: impl AsFd, title: &str) -> Result<String> {
+        let reader = duplicate_reader(source.as_fd())?;
+        self.print_reader_via_socket(reader, title)
+            .map(|result| result.job_id)
+    }
+
+    /// Like [`Printer::print_file`], but also reports the document size.
+    ///
+    /// cpdb-libs reads the file itself on this path, so `bytes_sent` is the
+    /// file's size at submission time rather than a streamed count. Use
+    /// [`Printer::print_file`] when only the job ID is needed.
+    pub fn print_file_accounted(&self, file_path: impl AsRef<Path>) -> Result<PrintResult> {
+        let file_path = file_path.as_ref();
+        let bytes_sent = std::fs::metadata(file_path)?.len();
+        let job_id = self.print_file(file_path)?;
+        Ok(PrintResult { job_id, bytes_sent })
+    }
+
     /// Submits a job with a per-call set of options and an explicit title.
     ///
     /// Options are applied to the printer's settings table via
@@ -363,20 +708,35 @@ impl<'frontend> Printer<'frontend> {
     /// this job and persist for subsequent jobs on the same printer until
     /// cleared via [`Printer::clear_setting`].
     ///
+    /// Every string is converted before anything is applied, so an option
+    /// key or value with an interior NUL byte fails with
+    /// [`CpdbError::NulError`] without touching the printer's settings.
+    ///
     /// Returns the backend-assigned job ID string.
+    ///
+    /// There is no job object to track or cancel afterwards: cpdb-libs
+    /// 2.x has no job API (no `cpdbNewPrintJob`, `cpdbCancelJobById`, or
+    /// job listing), so this crate offers no `PrintJob` type. The ID is
+    /// for logging and for the backend's own tools, e.g. `cancel <id>`
+    /// with CUPS.
     pub fn submit_job(
         &self,
-        file_path: &str,
+        file_path: impl AsRef<Path>,
         options: &[(&str, &str)],
         title: &str,
     ) -> Result<String> {
-        let c_path = CString::new(file_path)?;
+        let c_path = util::path_to_cstring(file_path.as_ref())?;
         let c_title = CString::new(title)?;
-        for (key, value) in options {
-            let k = CString::new(*key)?;
-            let v = CString::new(*value)?;
-            // SAFETY: pointers are non-null; CStrings live until end of loop body.
+        let c_options = options
+            .iter()
+            .map(|(key, value)| Ok((CString::new(*key)?, CString::new(*value)?)))
+            .collect::<Result<Vec<_>>>()?;
+        self.check_account_id(options)?;
+        self.reapply_runtime_settings()?;
+        for ((k, v), (key, value)) in c_options.iter().zip(options) {
+            // SAFETY: pointers are non-null; the CStrings outlive the call.
             unsafe { ffi::cpdbAddSettingToPrinter(self.raw.as_ptr(), k.as_ptr(), v.as_ptr()) };
+            self.record_runtime_setting(key, Some(value));
         }
         // SAFETY: cpdbPrintFileWithJobTitle returns a `g_strdup`'d job ID we own.
         unsafe {
@@ -390,6 +750,28 @@ impl<'frontend> Printer<'frontend> {
         }
     }
 
+    /// Submits a job with `settings` applied for this submission only.
+    ///
+    /// The settings are laid over the printer's own for the duration of the
+    /// call and the previous values are restored afterwards — including on
+    /// error — so nothing leaks into later jobs and the printer's
+    /// [`Printer::get_current`] values are unchanged once this returns.
+    /// `settings` is only read; a server can share one [`Settings`] across
+    /// every submission.
+    ///
+    /// Returns the backend-assigned job ID string.
+    pub fn submit_with(
+        &self,
+        file_path: impl AsRef<Path>,
+        settings: &Settings,
+        title: &str,
+    ) -> Result<String> {
+        let overlay = SettingsOverlay::apply(self, settings.entries())?;
+        let job_id = self.submit_job(file_path, &[], title);
+        drop(overlay);
+        job_id
+    }
+
     // ─── Options ─────────────────────────────────────────────────────────────
 
     /// Returns the default value for a named option, if the option exists.
@@ -423,8 +805,12 @@ impl<'frontend> Printer<'frontend> {
     }
 
     /// Returns the *current* (setting-or-default) value for a named option.
+    ///
+    /// This is the value a job would use; runtime settings are re-applied
+    /// first, see [the precedence rules](self#option-precedence).
     pub fn get_current(&self, option_name: &str) -> Result<String> {
         let c_name = CString::new(option_name)?;
+        self.reapply_runtime_settings()?;
         // SAFETY: `cpdbGetCurrent` returns a `g_strdup`'d string we own.
         unsafe {
             let v = ffi::cpdbGetCurrent(self.raw.as_ptr(), c_name.as_ptr());
@@ -467,6 +853,81 @@ impl<'frontend> Printer<'frontend> {
         }
     }
 
+    /// Returns an owned copy of a single option entry, or `None` when the
+    /// printer does not expose `option_name`.
+    pub fn get_option_info(&self, option_name: &str) -> Result<Option<OptionInfo>> {
+        let c_name = CString::new(option_name)?;
+        // SAFETY: `cpdbGetOption` returns a borrowed pointer into the
+        // printer's option table; `option_info_from_raw` copies every field
+        // before returning and never frees.
+        unsafe {
+            let opt = ffi::cpdbGetOption(self.raw.as_ptr(), c_name.as_ptr());
+            if opt.is_null() {
+                Ok(None)
+            } else {
+                Ok(Some(options::option_info_from_raw(opt)))
+            }
+        }
+    }
+
+    /// Every value the printer supports for `option_name`.
+    ///
+    /// Returns an empty list when the option is not exposed.
+    pub fn supported_values(&self, option_name: &str) -> Result<Vec<String>> {
+        Ok(self
+            .get_option_info(option_name)?
+            .map(|info| info.supported_values)
+            .unwrap_or_default())
+    }
+
+    /// The choices of `option_name` that are usable on this unit right now.
+    ///
+    /// IPP separates what a model *supports* (`media`, `output-bin`, ...)
+    /// from what is fitted or loaded on this printer, reported as
+    /// `<option>-ready` (e.g. `media-ready` for the paper in the trays) or
+    /// `<option>-installed`. Where the backend exposes either, only
+    /// supported choices that also appear there are returned, in supported
+    /// order — a dialog can grey out the rest, such as a duplexer that is
+    /// not installed. When neither is reported this falls back to
+    /// [`Printer::supported_values`].
+    pub fn available_choices(&self, option_name: &str) -> Result<Vec<String>> {
+        let supported = self.supported_values(option_name)?;
+        for suffix in ["ready", "installed"] {
+            if let Some(installed) = self.get_option_info(&format!("{option_name}-{suffix}"))? {
+                return Ok(filter_available(supported, &installed));
+            }
+        }
+        Ok(supported)
+    }
+
+    /// The MIME types the printer accepts, from its `document-format`
+    /// option.
+    ///
+    /// Returns an empty list when the backend does not expose the option.
+    pub fn supported_document_formats(&self) -> Result<Vec<String>> {
+        self.supported_values("document-format")
+    }
+
+    /// `true` when the printer lists `application/pdf` among its
+    /// [supported document formats](Printer::supported_document_formats).
+    ///
+    /// A printer that lists no formats reports `false`: whether a backend
+    /// converts PDF for it cannot be known in advance.
+    pub fn accepts_pdf(&self) -> Result<bool> {
+        Ok(lists_format(
+            &self.supported_document_formats()?,
+            "application/pdf",
+        ))
+    }
+
+    /// Sets `option_name` to `value` after checking it against the
+    /// printer's supported values.
+    fn set_supported_choice(&self, option_name: &str, value: &str) -> Result<()> {
+        let info = self.get_option_info(option_name)?;
+        check_choice(option_name, value, info.as_ref())?;
+        self.add_setting(option_name, value)
+    }
+
     /// Returns an owned snapshot of every option on this printer.
     ///
     /// Call [`Printer::acquire_details`] before this so the option table is
@@ -486,6 +947,81 @@ impl<'frontend> Printer<'frontend> {
         }
     }
 
+    /// Every option the printer reports, sorted by name.
+    ///
+    /// Unlike [`Printer::get_options_collection`], a printer whose option
+    /// table is missing or empty — e.g. before
+    /// [`Printer::acquire_details`] has completed — yields an empty list
+    /// rather than an error.
+    pub fn get_all_options(&self) -> Result<Vec<OptionInfo>> {
+        // SAFETY: `cpdbGetAllOptions` returns a borrowed pointer to the
+        // printer's `options` field; `from_raw` copies everything out.
+        let opts = unsafe { ffi::cpdbGetAllOptions(self.raw.as_ptr()) };
+        let Some(opts) = NonNull::new(opts) else {
+            return Ok(Vec::new());
+        };
+        // SAFETY: `opts` is the printer's live, initialised options struct.
+        let mut options = unsafe { OptionsCollection::from_raw(opts) }.options;
+        options.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(options)
+    }
+
+    /// Renders every option as one `name: current (default) [supported...]`
+    /// line, sorted by name, for logs and CLI output.
+    ///
+    /// "current" is the printer's setting for the option, or the default
+    /// when unset. Reads the option table once; like
+    /// [`Printer::get_options_collection`], it needs
+    /// [`Printer::acquire_details`] to have populated the table.
+    pub fn format_options(&self) -> Result<String> {
+        let collection = self.get_options_collection()?;
+        let mut infos: Vec<&OptionInfo> = collection.iter().collect();
+        infos.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut lines = Vec::with_capacity(infos.len());
+        for info in infos {
+            let current = self
+                .get_setting(&info.name)?
+                .unwrap_or_else(|| info.default_value.clone());
+            lines.push(options::format_option_line(info, &current));
+        }
+        Ok(lines.join("\n"))
+    }
+
+    // ─── Typed defaults ──────────────────────────────────────────────────────
+
+    /// The printer's default `copies`, for preselecting a dialog.
+    ///
+    /// Falls back to 1 when the printer reports no default or one that is
+    /// not a positive integer.
+    pub fn default_copies(&self) -> Result<u32> {
+        Ok(parse_default_copies(self.get_option("copies")?.as_deref()))
+    }
+
+    /// The printer's default `sides`.
+    ///
+    /// Falls back to [`Sides::OneSided`] when no default is reported or
+    /// the keyword is not recognised.
+    pub fn default_sides(&self) -> Result<Sides> {
+        Ok(self
+            .get_option("sides")?
+            .as_deref()
+            .and_then(Sides::from_keyword)
+            .unwrap_or(Sides::OneSided))
+    }
+
+    /// The printer's default `orientation-requested`.
+    ///
+    /// Falls back to [`Orientation::Portrait`] when no default is reported
+    /// or it is not one of the four orientations (e.g. `none`).
+    pub fn default_orientation(&self) -> Result<Orientation> {
+        Ok(self
+            .get_option("orientation-requested")?
+            .as_deref()
+            .and_then(Orientation::parse)
+            .unwrap_or(Orientation::Portrait))
+    }
+
     // ─── Per-printer settings ────────────────────────────────────────────────
 
     /// Reads a per-printer setting, or returns `None` when unset.
@@ -504,7 +1040,19 @@ impl<'frontend> Printer<'frontend> {
     }
 
     /// Inserts or overwrites a per-printer setting.
+    ///
+    /// The value is a runtime setting: it takes precedence over saved
+    /// settings cpdb-libs merges in later, see
+    /// [the precedence rules](self#option-precedence).
     pub fn add_setting(&self, name: &str, value: &str) -> Result<()> {
+        self.write_setting(name, value)?;
+        self.record_runtime_setting(name, Some(value));
+        Ok(())
+    }
+
+    /// Writes a setting into the printer's table without recording it as
+    /// a runtime setting.
+    fn write_setting(&self, name: &str, value: &str) -> Result<()> {
         let c_name = CString::new(name)?;
         let c_val = CString::new(value)?;
         // SAFETY: pointers are non-null; the CStrings outlive the call.
@@ -512,8 +1060,60 @@ impl<'frontend> Printer<'frontend> {
         Ok(())
     }
 
-    /// Removes a per-printer setting. Returns `Ok(true)` when it existed.
+    /// The printer's current settings as the `GVariant` cpdb-libs sends to
+    /// the backend with a job.
+    ///
+    /// The variant has type `a(ss)`: one `(option, value)` pair per
+    /// setting, in unspecified order. A printer with no settings yields the
+    /// sentinel `[("NA", "NA")]`. Intended for forwarding to other D-Bus
+    /// services or inspecting what a job will carry.
+    pub fn options_as_gvariant(&self) -> Result<GVariantRef> {
+        // SAFETY: pointer is non-null; reading the `settings` field is sound.
+        let settings = unsafe { (*self.raw.as_ptr()).settings };
+        if settings.is_null() {
+            return Err(CpdbError::BackendError(
+                "printer has no settings table".into(),
+            ));
+        }
+        // SAFETY: `settings` is the printer's live table; cpdb-libs returns
+        // a new floating variant, which `from_raw_full` sinks and owns.
+        unsafe {
+            let raw = ffi::cpdbSerializeToGVariant(settings);
+            GVariantRef::from_raw_full(raw as *mut glib_sys::GVariant)
+        }
+    }
+
+    /// Merges saved settings into this printer without overriding runtime
+    /// values, e.g. ones read with [`Settings::read_from_disk`] into a
+    /// frontend built with
+    /// [`crate::FrontendBuilder::ignore_last_saved_settings`].
+    ///
+    /// Keys that have a runtime setting are skipped; the rest are applied
+    /// as saved values, so a later [`Printer::add_setting`] still wins.
+    /// Returns the number of keys applied. See
+    /// [the precedence rules](self#option-precedence).
+    pub fn apply_saved_settings(&self, saved: &Settings) -> Result<usize> {
+        let mut applied = 0;
+        for (key, value) in saved.entries() {
+            if self.runtime_setting(&key).is_none() {
+                self.write_setting(&key, &value)?;
+                applied += 1;
+            }
+        }
+        Ok(applied)
+    }
+
+    /// Removes a per-printer setting, including its runtime record. Returns
+    /// `Ok(true)` when it existed.
     pub fn clear_setting(&self, name: &str) -> Result<bool> {
+        let existed = self.erase_setting(name)?;
+        self.record_runtime_setting(name, None);
+        Ok(existed)
+    }
+
+    /// Removes a setting from the printer's table, leaving the runtime
+    /// record alone.
+    fn erase_setting(&self, name: &str) -> Result<bool> {
         let c_name = CString::new(name)?;
         // SAFETY: pointers are non-null; the CString outlives the call.
         let existed =
@@ -521,8 +1121,279 @@ impl<'frontend> Printer<'frontend> {
         Ok(existed != 0)
     }
 
+    /// Where this printer's runtime settings are recorded.
+    fn runtime_key(&self) -> RuntimeKey {
+        match (self.frontend, self.id(), self.backend_name()) {
+            (Some(frontend), Ok(id), Ok(backend)) => RuntimeKey::Table {
+                frontend: frontend.as_ptr() as usize,
+                backend,
+                id,
+            },
+            _ => RuntimeKey::Object(self.raw.as_ptr() as usize),
+        }
+    }
+
+    /// The recorded runtime value of `name`, if any.
+    fn runtime_setting(&self, name: &str) -> Option<String> {
+        runtime_settings()
+            .get(&self.runtime_key())?
+            .get(name)
+            .cloned()
+    }
+
+    /// Records (`Some`) or forgets (`None`) the runtime value of `name`,
+    /// returning the previous record.
+    fn record_runtime_setting(&self, name: &str, value: Option<&str>) -> Option<String> {
+        let key = self.runtime_key();
+        let mut registry = runtime_settings();
+        match value {
+            Some(value) => registry
+                .entry(key)
+                .or_default()
+                .insert(name.to_owned(), value.to_owned()),
+            None => {
+                let entry = registry.get_mut(&key)?;
+                let previous = entry.remove(name);
+                if entry.is_empty() {
+                    registry.remove(&key);
+                }
+                previous
+            }
+        }
+    }
+
+    /// Writes every recorded runtime setting back into the printer's
+    /// table, over any saved value cpdb-libs merged in since.
+    pub(crate) fn reapply_runtime_settings(&self) -> Result<()> {
+        let entries: Vec<(String, String)> = runtime_settings()
+            .get(&self.runtime_key())
+            .map(|entry| entry.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+            .unwrap_or_default();
+        for (name, value) in entries {
+            self.write_setting(&name, &value)?;
+        }
+        Ok(())
+    }
+
+    // ─── Output ──────────────────────────────────────────────────────────────
+
+    /// The output bins (stackers) the printer advertises via `output-bin`.
+    ///
+    /// Returns an empty list for single-bin printers and printers that do
+    /// not expose the option.
+    pub fn output_bins(&self) -> Result<Vec<String>> {
+        let bins = self.supported_values("output-bin")?;
+        if bins.len() < 2 {
+            return Ok(Vec::new());
+        }
+        Ok(bins)
+    }
+
+    /// Directs subsequent jobs to the named output bin.
+    ///
+    /// Fails with [`CpdbError::OptionError`] when `name` is not one of the
+    /// printer's supported `output-bin` values.
+    pub fn set_output_bin(&self, name: &str) -> Result<()> {
+        self.set_supported_choice("output-bin", name)
+    }
+
+    /// The positioned finishings of type `finishing` the printer supports.
+    ///
+    /// Read from `finishings-col` when the printer advertises it, otherwise
+    /// from the plain `finishings` keywords. Empty when neither lists a
+    /// positioned variant.
+    pub fn finishings_positions(&self, finishing: FinishingType) -> Result<Vec<FinishingsCol>> {
+        let mut values = self.supported_values("finishings-col")?;
+        if values.is_empty() {
+            values = self.supported_values("finishings")?;
+        }
+        let mut cols = Vec::new();
+        for col in values
+            .iter()
+            .filter_map(|v| FinishingsCol::from_template(strip_collection(v)))
+        {
+            if col.finishing == finishing && !cols.contains(&col) {
+                cols.push(col);
+            }
+        }
+        Ok(cols)
+    }
+
+    /// Requests a positioned finishing, e.g. a staple in the top-left
+    /// corner.
+    ///
+    /// Sets `finishings-col` when the printer supports the requested
+    /// template there, and falls back to the equivalent plain `finishings`
+    /// keyword otherwise. Fails with [`CpdbError::OptionError`] when
+    /// neither option supports it.
+    pub fn set_finishings_col(&self, col: FinishingsCol) -> Result<()> {
+        let col_info = self.get_option_info("finishings-col")?;
+        let plain_info = self.get_option_info("finishings")?;
+        let (option, value) = resolve_finishings(col, col_info.as_ref(), plain_info.as_ref())?;
+        self.add_setting(option, &value)
+    }
+
+    // ─── Layout ──────────────────────────────────────────────────────────────
+
+    /// Requests simplex or duplex printing.
+    ///
+    /// Fails with [`CpdbError::OptionError`] when `sides` is not among the
+    /// printer's supported values (a printer without the option supports
+    /// only one-sided). A printer that only supports manual duplex would
+    /// otherwise print the job single-sided without saying so.
+    pub fn set_sides(&self, sides: Sides) -> Result<()> {
+        self.apply_sides(sides, false).map(|_| ())
+    }
+
+    /// Like [`Printer::set_sides`], but falls back to one-sided printing
+    /// when `sides` is unsupported.
+    ///
+    /// Returns the mode actually applied, so callers can tell the user
+    /// that duplex will not happen.
+    pub fn set_sides_with_fallback(&self, sides: Sides) -> Result<Sides> {
+        self.apply_sides(sides, true)
+    }
+
+    fn apply_sides(&self, sides: Sides, fall_back: bool) -> Result<Sides> {
+        let info = self.get_option_info("sides")?;
+        let effective = resolve_sides(sides, info.as_ref(), fall_back)?;
+        if effective != sides {
+            log::debug!("sides '{sides}' unsupported, falling back to '{effective}'");
+        }
+        if info.is_some() {
+            self.add_setting("sides", effective.as_str())?;
+        }
+        Ok(effective)
+    }
+
+    /// The page orders the printer supports for `number-up` layouts
+    /// (`presentation-direction-number-up`). Unrecognised keywords are
+    /// skipped; empty when the option is not exposed.
+    pub fn presentation_directions(&self) -> Result<Vec<Direction>> {
+        Ok(self
+            .supported_values("presentation-direction-number-up")?
+            .iter()
+            .filter_map(|v| Direction::from_keyword(v))
+            .collect())
+    }
+
+    /// Sets the order in which pages are placed on each sheet, e.g.
+    /// [`Direction::ToLeftToBottom`] for right-to-left locales.
+    ///
+    /// Only has a visible effect when `number-up` is greater than 1; set
+    /// that separately. Fails with [`CpdbError::OptionError`] when the
+    /// printer does not support `direction`.
+    pub fn set_presentation_direction(&self, direction: Direction) -> Result<()> {
+        self.set_supported_choice("presentation-direction-number-up", direction.as_str())
+    }
+
+    // ─── Accounting ──────────────────────────────────────────────────────────
+
+    /// Tags subsequent jobs with an accounting ID (`job-account-id`).
+    ///
+    /// The value is read back after being set; a mismatch is reported as
+    /// [`CpdbError::OptionError`]. IDs containing NUL bytes are rejected
+    /// with [`CpdbError::NulError`]. To refuse untagged jobs at submission
+    /// time, build the handle with [`Printer::require_account_id`].
+    pub fn set_account_id(&self, id: &str) -> Result<()> {
+        self.add_setting(JOB_ACCOUNT_ID, id)?;
+        match self.get_setting(JOB_ACCOUNT_ID)? {
+            Some(stored) if stored == id => Ok(()),
+            _ => Err(CpdbError::OptionError(format!(
+                "{JOB_ACCOUNT_ID} '{id}' was not retained by the printer"
+            ))),
+        }
+    }
+
+    /// Makes every submission through this handle fail with
+    /// [`CpdbError::OptionError`] unless a `job-account-id` is set, either
+    /// via [`Printer::set_account_id`] or in the job's own options.
+    ///
+    /// The check runs before anything is sent to the backend, in
+    /// [`Printer::print_file`], [`Printer::submit_job`],
+    /// [`Printer::submit_with`], [`Printer::print_fd`],
+    /// [`Printer::print_socket`] and every path built on them. The flag
+    /// belongs to this handle and its [views](Printer::view); handles
+    /// looked up again from the frontend start without it.
+    pub fn require_account_id(mut self) -> Self {
+        self.account_required = true;
+        self
+    }
+
+    /// Fails when [`Printer::require_account_id`] is set and neither
+    /// `options` nor the printer's settings carry an accounting ID.
+    fn check_account_id(&self, options: &[(&str, &str)]) -> Result<()> {
+        if !self.account_required {
+            return Ok(());
+        }
+        let in_options = options
+            .iter()
+            .find(|(key, _)| *key == JOB_ACCOUNT_ID)
+            .map(|(_, value)| (*value).to_owned());
+        let current = match in_options {
+            Some(value) => Some(value),
+            None => self.account_id()?,
+        };
+        check_account_id_present(current.as_deref())
+    }
+
+    /// The accounting ID set via [`Printer::set_account_id`], or `None`
+    /// when jobs are untagged. [`Printer::get_current`] with
+    /// `"job-account-id"` reports the same value.
+    pub fn account_id(&self) -> Result<Option<String>> {
+        self.get_setting(JOB_ACCOUNT_ID)
+    }
+
+    // ─── Authentication ──────────────────────────────────────────────────────
+
+    /// `true` when the queue demands authentication, per the printer's
+    /// `uri-authentication-supported` attribute.
+    ///
+    /// Open queues — those reporting `none` or `requesting-user-name`
+    /// only, or not reporting the attribute at all — return `false`.
+    pub fn requires_auth(&self) -> Result<bool> {
+        Ok(self
+            .get_option_info("uri-authentication-supported")?
+            .is_some_and(|info| auth_required(&info)))
+    }
+
+    /// Sets the user name sent with subsequent jobs
+    /// (`requesting-user-name`), as `domain\user` when a domain is given.
+    ///
+    /// This is the only credential cpdb-libs can forward: it has no
+    /// password, Kerberos, or certificate support, so queues needing
+    /// `basic`, `digest`, `negotiate`, or `certificate` authentication
+    /// must be authenticated by the backend itself (e.g. CUPS' own
+    /// credential store).
+    pub fn set_auth(&self, user: &str, domain: Option<&str>) -> Result<()> {
+        let name = match domain {
+            Some(domain) => format!("{domain}\\{user}"),
+            None => user.to_owned(),
+        };
+        self.add_setting("requesting-user-name", &name)
+    }
+
     // ─── Media ───────────────────────────────────────────────────────────────
 
+    /// The paper stocks the printer advertises via `media-type` — e.g.
+    /// `stationery`, `photographic-glossy`, `transparency`.
+    ///
+    /// This is independent of the media *size* (`media`). Returns an empty
+    /// list when the printer does not expose media types.
+    pub fn media_types(&self) -> Result<Vec<String>> {
+        self.supported_values("media-type")
+    }
+
+    /// Selects the paper stock for subsequent jobs.
+    ///
+    /// cpdb-libs flattens IPP's `media-col` into separate options, so the
+    /// stock is set on its own and combines with whatever `media` size is
+    /// selected. Fails with [`CpdbError::OptionError`] when `media_type` is
+    /// not one of the printer's supported values.
+    pub fn set_media_type(&self, media_type: &str) -> Result<()> {
+        self.set_supported_choice("media-type", media_type)
+    }
+
     /// Returns the descriptive name of a media type, if known.
     pub fn get_media(&self, media_name: &str) -> Result<Option<String>> {
         let c_name = CString::new(media_name)?;
@@ -570,18 +1441,49 @@ impl<'frontend> Printer<'frontend> {
         if count <= 0 || raw_margins.is_null() {
             return Err(CpdbError::NotFound(format!("media margins '{media_name}'")));
         }
-        let mut out = Vec::with_capacity(count as usize);
         // SAFETY: cpdb-libs guarantees `count` valid entries at `raw_margins`.
-        for i in 0..(count as isize) {
-            let m = unsafe { &*raw_margins.offset(i) };
-            out.push(Margin {
-                top: m.top,
-                bottom: m.bottom,
-                left: m.left,
-                right: m.right,
-            });
+        let entries = unsafe { margins_from_raw(raw_margins, count) };
+        Ok(Margins { entries })
+    }
+
+    /// Every media size the printer advertises, with dimensions and
+    /// margins, sorted by name.
+    ///
+    /// Like [`Printer::get_all_options`], this reads the table filled in by
+    /// [`Printer::acquire_details`] and returns an empty list when the
+    /// printer reports no media.
+    pub fn list_media(&self) -> Result<Vec<MediaInfo>> {
+        // SAFETY: `cpdbGetAllOptions` returns a borrowed pointer to the
+        // printer's `options` field.
+        let opts = unsafe { ffi::cpdbGetAllOptions(self.raw.as_ptr()) };
+        if opts.is_null() {
+            return Ok(Vec::new());
         }
-        Ok(Margins { entries: out })
+        // SAFETY: `opts` is the printer's live options struct.
+        let table = unsafe { (*opts).media } as *mut glib_sys::GHashTable;
+        if table.is_null() {
+            return Ok(Vec::new());
+        }
+
+        let mut media = Vec::new();
+        // SAFETY: iterator is initialised on the stack and iterated
+        // synchronously; every value is a `cpdb_media_t` owned by the table
+        // and copied out before returning.
+        unsafe {
+            let mut iter = MaybeUninit::<glib_sys::GHashTableIter>::uninit();
+            glib_sys::g_hash_table_iter_init(iter.as_mut_ptr(), table);
+            let mut iter = iter.assume_init();
+
+            let mut key: glib_sys::gpointer = std::ptr::null_mut();
+            let mut value: glib_sys::gpointer = std::ptr::null_mut();
+            while glib_sys::g_hash_table_iter_next(&mut iter, &mut key, &mut value) != 0 {
+                if let Some(info) = media_info_from_raw(value as *const ffi::cpdb_media_t) {
+                    media.push(info);
+                }
+            }
+        }
+        media.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(media)
     }
 
     // ─── Translations ────────────────────────────────────────────────────────
@@ -727,6 +1629,55 @@ impl<'frontend> Printer<'frontend> {
         }
     }
 
+    /// The label for `option` in the locale translations were last
+    /// acquired for.
+    ///
+    /// Passes `option` through unchanged when no translations have been
+    /// acquired yet or the backend has no label for it, so the result can
+    /// always be displayed. Translations served from a [`TranslationCache`]
+    /// by [`Printer::all_translations_cached`] do not count as acquired
+    /// for this printer; use the returned map for those.
+    pub fn option_translation(&self, option: &str) -> Result<String> {
+        let translated = match self.translation_locale() {
+            Some(locale) => self.get_option_translation(option, &locale)?,
+            None => None,
+        };
+        Ok(translated.unwrap_or_else(|| option.to_owned()))
+    }
+
+    /// The label for `choice` of `option`, falling back to `choice` as
+    /// [`Printer::option_translation`] does.
+    pub fn choice_translation(&self, option: &str, choice: &str) -> Result<String> {
+        let translated = match self.translation_locale() {
+            Some(locale) => self.get_choice_translation(option, choice, &locale)?,
+            None => None,
+        };
+        Ok(translated.unwrap_or_else(|| choice.to_owned()))
+    }
+
+    /// The label for an option group, falling back to `group` as
+    /// [`Printer::option_translation`] does.
+    pub fn group_translation(&self, group: &str) -> Result<String> {
+        let translated = match self.translation_locale() {
+            Some(locale) => self.get_group_translation(group, &locale)?,
+            None => None,
+        };
+        Ok(translated.unwrap_or_else(|| group.to_owned()))
+    }
+
+    /// The locale cpdb-libs recorded when translations were last acquired.
+    fn translation_locale(&self) -> Option<String> {
+        // SAFETY: reading the `locale` field of a live printer object.
+        let locale = unsafe { (*self.raw.as_ptr()).locale };
+        if locale.is_null() {
+            return None;
+        }
+        // SAFETY: non-null, NUL-terminated and owned by the printer.
+        unsafe { util::cstr_to_string(locale) }
+            .ok()
+            .filter(|l| !l.is_empty())
+    }
+
     /// Synchronously populates every translation for the given locale.
     pub fn get_all_translations(&self, locale: &str) -> Result<()> {
         let c_locale = CString::new(locale)?;
@@ -735,6 +1686,40 @@ impl<'frontend> Printer<'frontend> {
         Ok(())
     }
 
+    /// Returns every translation for `locale`, consulting `cache` first.
+    ///
+    /// On a miss this calls [`Printer::get_all_translations`] and stores
+    /// the resulting table under this printer's make and model, so other
+    /// printers of the same model are served without a D-Bus round trip.
+    /// Empty tables are not cached. See the
+    /// [`translation_cache`](crate::translation_cache) module for the
+    /// same-model assumption this relies on.
+    ///
+    /// A cache hit does not fill this printer's own translation table, so
+    /// [`Printer::option_translation`] and its siblings keep returning
+    /// untranslated names for it. Look labels up in the returned map with
+    /// [`TranslationMap::get`] instead.
+    pub fn all_translations_cached(
+        &self,
+        locale: &str,
+        cache: &TranslationCache,
+    ) -> Result<TranslationMap> {
+        let model = self.make_and_model().unwrap_or_default();
+        if model.is_empty() {
+            self.get_all_translations(locale)?;
+            return Ok(self.translations());
+        }
+        if let Some(map) = cache.get(&model, locale) {
+            return Ok(map);
+        }
+        self.get_all_translations(locale)?;
+        let map = self.translations();
+        if !map.is_empty() {
+            cache.insert(&model, locale, map.clone());
+        }
+        Ok(map)
+    }
+
     /// Returns an owned snapshot of the printer's cached translation table.
     ///
     /// Call [`Printer::get_all_translations`] (or
@@ -807,13 +1792,25 @@ impl<'frontend> Printer<'frontend> {
     // ─── Persistence ─────────────────────────────────────────────────────────
 
     /// Serialises this printer to a file (`cpdbPicklePrinterToFile`).
-    pub fn pickle_to_file(&self, path: &str, frontend: &Frontend) -> Result<()> {
-        let c_path = CString::new(path)?;
+    ///
+    /// The C function reports nothing, so success is verified by checking
+    /// that `path` exists and is non-empty afterwards; otherwise this fails
+    /// with [`CpdbError::PrinterError`]. Pass a fresh path: a stale file
+    /// already at `path` would pass the check.
+    pub fn pickle_to_file(&self, path: impl AsRef<Path>, frontend: &Frontend) -> Result<()> {
+        let path = path.as_ref();
+        let c_path = util::path_to_cstring(path)?;
         // SAFETY: both pointers are non-null and live for the duration of the call.
         unsafe {
             ffi::cpdbPicklePrinterToFile(self.raw.as_ptr(), c_path.as_ptr(), frontend.as_raw());
         }
-        Ok(())
+        match std::fs::metadata(path) {
+            Ok(meta) if meta.len() > 0 => Ok(()),
+            _ => Err(CpdbError::PrinterError(format!(
+                "printer was not written to {}",
+                path.display()
+            ))),
+        }
     }
 
     /// Loads a printer that was previously serialised via [`pickle_to_file`].
@@ -821,17 +1818,342 @@ impl<'frontend> Printer<'frontend> {
     /// The returned printer is *owned* — it is freed when dropped.
     ///
     /// [`pickle_to_file`]: Self::pickle_to_file
-    pub fn load_from_file(path: &str) -> Result<Printer<'static>> {
-        let c_path = CString::new(path)?;
+    pub fn load_from_file(path: impl AsRef<Path>) -> Result<Printer<'static>> {
+        let path = path.as_ref();
+        let c_path = util::path_to_cstring(path)?;
         // SAFETY: `cpdbResurrectPrinterFromFile` returns an owned printer.
         let raw = unsafe { ffi::cpdbResurrectPrinterFromFile(c_path.as_ptr()) };
         if raw.is_null() {
-            return Err(CpdbError::NotFound(format!("pickled printer at {path}")));
+            return Err(CpdbError::NotFound(format!(
+                "pickled printer at {}",
+                path.display()
+            )));
         }
         Printer::<'static>::from_raw_owned(raw)
     }
 }
 
+/// Checks `value` against the supported values of `option`.
+///
+/// `info` is `None` when the printer does not expose the option at all.
+fn check_choice(option: &str, value: &str, info: Option<&OptionInfo>) -> Result<()> {
+    match info {
+        None => Err(CpdbError::OptionError(format!(
+            "printer does not support option '{option}'"
+        ))),
+        Some(info) if info.supports(value) => Ok(()),
+        Some(info) => Err(CpdbError::OptionError(format!(
+            "unsupported {option} '{value}' (supported: {})",
+            info.supported_values.join(", ")
+        ))),
+    }
+}
+
+/// `true` when `uri-authentication-supported` lists a scheme beyond
+/// `none` / `requesting-user-name`.
+fn auth_required(info: &OptionInfo) -> bool {
+    info.supported_values
+        .iter()
+        .chain(std::iter::once(&info.default_value))
+        .map(|v| v.trim())
+        .any(|v| !v.is_empty() && v != "NA" && v != "none" && v != "requesting-user-name")
+}
+
+/// The IPP job attribute used for cost accounting.
+const JOB_ACCOUNT_ID: &str = "job-account-id";
+
+/// Rejects a submission whose accounting ID is missing or empty.
+fn check_account_id_present(account_id: Option<&str>) -> Result<()> {
+    match account_id {
+        Some(id) if !id.trim().is_empty() && id != "NA" => Ok(()),
+        _ => Err(CpdbError::OptionError(format!(
+            "{JOB_ACCOUNT_ID} is required but not set"
+        ))),
+    }
+}
+
+/// The document sent by [`Printer::print_test_page`].
+const TEST_PAGE_PS: &[u8] = include_bytes!("../assets/test-page.ps");
+
+/// A key's table value and runtime record before an overlay changed it.
+struct Overlaid {
+    key: String,
+    value: Option<String>,
+    runtime: Option<String>,
+}
+
+/// Settings temporarily applied to a printer; the previous values, and
+/// their runtime records, are restored when the overlay is dropped.
+struct SettingsOverlay<'a, 'frontend> {
+    printer: &'a Printer<'frontend>,
+    previous: Vec<Overlaid>,
+}
+
+impl<'a, 'frontend> SettingsOverlay<'a, 'frontend> {
+    /// Applies `entries` to `printer`, remembering what each key held.
+    fn apply(printer: &'a Printer<'frontend>, entries: Vec<(String, String)>) -> Result<Self> {
+        let mut overlay = Self {
+            printer,
+            previous: Vec::with_capacity(entries.len()),
+        };
+        for (key, value) in entries {
+            let before = printer.get_setting(&key)?;
+            printer.write_setting(&key, &value)?;
+            // Recorded too, so the overlay survives the re-application of
+            // runtime settings at submission.
+            let runtime = printer.record_runtime_setting(&key, Some(&value));
+            overlay.previous.push(Overlaid {
+                key,
+                value: before,
+                runtime,
+            });
+        }
+        Ok(overlay)
+    }
+}
+
+impl Drop for SettingsOverlay<'_, '_> {
+    fn drop(&mut self) {
+        // Reverse order, so a key applied twice ends up at its original value.
+        for Overlaid {
+            key,
+            value,
+            runtime,
+        } in self.previous.drain(..).rev()
+        {
+            // Keys already round-tripped through CString, so these cannot fail.
+            let _ = match value {
+                Some(value) => self.printer.write_setting(&key, &value),
+                None => self.printer.erase_setting(&key).map(|_| ()),
+            };
+            self.printer
+                .record_runtime_setting(&key, runtime.as_deref());
+        }
+    }
+}
+
+/// Identifies a printer in the runtime-settings registry.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum RuntimeKey {
+    /// A printer in a frontend's table, by queue rather than object:
+    /// cpdb-libs replaces the object when it rediscovers the printer.
+    Table {
+        frontend: usize,
+        backend: String,
+        id: String,
+    },
+    /// A printer object outside any known table, e.g. loaded from a file.
+    Object(usize),
+}
+
+/// Runtime settings per printer, so they can be re-applied over saved
+/// settings. Global because `Printer` handles are transient views of
+/// objects cpdb-libs owns.
+fn runtime_settings() -> MutexGuard<'static, HashMap<RuntimeKey, HashMap<String, String>>> {
+    static R: OnceLock<Mutex<HashMap<RuntimeKey, HashMap<String, String>>>> = OnceLock::new();
+    // Recover from poisoning — the map holds plain strings, so a panic
+    // mid-update cannot leave it inconsistent.
+    R.get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+}
+
+/// Forgets the runtime settings of every printer in `frontend`'s table.
+/// Called when the frontend is dropped.
+pub(crate) fn forget_runtime_settings(frontend: *mut ffi::cpdb_frontend_obj_t) {
+    runtime_settings().retain(
+        |key, _| !matches!(key, RuntimeKey::Table { frontend: f, .. } if *f == frontend as usize),
+    );
+}
+
+/// Strips the `{finishing-template=...}` wrapper from a collection value,
+/// leaving bare keywords untouched.
+fn strip_collection(value: &str) -> &str {
+    value
+        .strip_prefix("{finishing-template=")
+        .and_then(|v| v.strip_suffix('}'))
+        .unwrap_or(value)
+}
+
+/// Picks the option and value to set for `col`: `finishings-col` when it
+/// supports the template, else the plain `finishings` keyword.
+fn resolve_finishings(
+    col: FinishingsCol,
+    col_info: Option<&OptionInfo>,
+    plain_info: Option<&OptionInfo>,
+) -> Result<(&'static str, String)> {
+    let template = col.template();
+    if let Some(info) = col_info {
+        if info
+            .supported_values
+            .iter()
+            .any(|v| strip_collection(v) == template)
+        {
+            return Ok(("finishings-col", col.to_collection_string()));
+        }
+    }
+    if plain_info.is_some_and(|info| info.supports(&template)) {
+        return Ok(("finishings", template));
+    }
+    Err(CpdbError::OptionError(format!(
+        "unsupported finishing '{template}'"
+    )))
+}
+
+/// Parses a `printer-up-time` value (whole seconds).
+fn parse_up_time(value: &str) -> Option<Duration> {
+    value.trim().parse::<u64>().ok().map(Duration::from_secs)
+}
+
+/// Checks a [`Printer::print_raw`] format, returning a warning when the
+/// printer does not advertise it.
+fn raw_format_warning(format: &str, supported: Option<&OptionInfo>) -> Result<Option<String>> {
+    let valid = format
+        .split_once('/')
+        .is_some_and(|(kind, sub)| !kind.is_empty() && !sub.is_empty() && !sub.contains('/'));
+    if !valid {
+        return Err(CpdbError::OptionError(format!(
+            "'{format}' is not a MIME type"
+        )));
+    }
+    Ok(match supported {
+        Some(info) if lists_format(&info.supported_values, format) => None,
+        Some(info) if !info.supported_values.is_empty() => Some(format!(
+            "'{format}' is not among the supported document formats; the backend may convert or reject it"
+        )),
+        _ => Some(format!(
+            "printer lists no document formats; cannot confirm '{format}' is passed through"
+        )),
+    })
+}
+
+/// `true` when `formats` contains the MIME type `wanted`. MIME types
+/// compare case-insensitively, and parameters such as `; charset=utf-8`
+/// are ignored on both sides.
+fn lists_format<S: AsRef<str>>(formats: &[S], wanted: &str) -> bool {
+    fn essence(format: &str) -> &str {
+        format.split(';').next().unwrap_or_default().trim()
+    }
+    let wanted = essence(wanted);
+    formats
+        .iter()
+        .any(|format| essence(format.as_ref()).eq_ignore_ascii_case(wanted))
+}
+
+/// Keeps the `supported` choices listed by an `-ready` / `-installed`
+/// option. Such options carry their list either as supported values or, for
+/// a single attribute, as a comma-separated default.
+fn filter_available(supported: Vec<String>, installed: &OptionInfo) -> Vec<String> {
+    let listed: Vec<&str> = if installed.supported_values.is_empty() {
+        installed
+            .default_value
+            .split(',')
+            .map(str::trim)
+            .filter(|v| !v.is_empty() && *v != "NA")
+            .collect()
+    } else {
+        installed
+            .supported_values
+            .iter()
+            .map(String::as_str)
+            .collect()
+    };
+    if supported.is_empty() {
+        return listed.into_iter().map(str::to_owned).collect();
+    }
+    supported
+        .into_iter()
+        .filter(|choice| listed.contains(&choice.as_str()))
+        .collect()
+}
+
+/// Parses a `copies` default, falling back to 1.
+fn parse_default_copies(value: Option<&str>) -> u32 {
+    value
+        .and_then(|v| v.trim().parse().ok())
+        .filter(|&n: &u32| n > 0)
+        .unwrap_or(1)
+}
+
+/// Picks the `sides` mode to apply for `requested`.
+///
+/// `info` is `None` for printers without a `sides` option, which can only
+/// print one-sided. With `fall_back`, an unsupported request resolves to
+/// [`Sides::OneSided`] instead of an error.
+fn resolve_sides(requested: Sides, info: Option<&OptionInfo>, fall_back: bool) -> Result<Sides> {
+    let supported = |sides: Sides| match info {
+        Some(info) => info.supports(sides.as_str()),
+        None => sides == Sides::OneSided,
+    };
+    if supported(requested) {
+        return Ok(requested);
+    }
+    if fall_back && supported(Sides::OneSided) {
+        return Ok(Sides::OneSided);
+    }
+    let available = info.map_or_else(
+        || Sides::OneSided.as_str().to_owned(),
+        |info| info.supported_values.join(", "),
+    );
+    Err(CpdbError::OptionError(format!(
+        "unsupported sides '{requested}' (supported: {available})"
+    )))
+}
+
+/// Copies `count` margins out of a cpdb-libs margin array.
+///
+/// # Safety
+/// `margins` must be null or point to at least `count` initialised entries.
+pub(crate) unsafe fn margins_from_raw(
+    margins: *const ffi::cpdb_margin_t,
+    count: i32,
+) -> Vec<Margin> {
+    if margins.is_null() || count <= 0 {
+        return Vec::new();
+    }
+    // SAFETY: caller contract.
+    let raw = unsafe { std::slice::from_raw_parts(margins, count as usize) };
+    raw.iter()
+        .map(|m| Margin {
+            top: m.top,
+            bottom: m.bottom,
+            left: m.left,
+            right: m.right,
+        })
+        .collect()
+}
+
+/// Copies a media entry out of cpdb-libs. Entries without a name are
+/// skipped.
+///
+/// # Safety
+/// `media` must be null or point to a live `cpdb_media_t` whose `margins`
+/// array holds `num_margins` entries.
+pub(crate) unsafe fn media_info_from_raw(media: *const ffi::cpdb_media_t) -> Option<MediaInfo> {
+    // SAFETY: caller contract.
+    let media = unsafe { media.as_ref() }?;
+    if media.name.is_null() {
+        return None;
+    }
+    // SAFETY: non-null, NUL-terminated and owned by the media entry.
+    let name = unsafe { CStr::from_ptr(media.name) }
+        .to_string_lossy()
+        .into_owned();
+    Some(MediaInfo {
+        name,
+        width: media.width,
+        length: media.length,
+        // SAFETY: caller contract.
+        margins: unsafe { margins_from_raw(media.margins, media.num_margins) },
+    })
+}
+
+/// A readable `File` over a duplicate of `source`, leaving the caller's
+/// descriptor open.
+fn duplicate_reader(source: BorrowedFd<'_>) -> Result<File> {
+    Ok(File::from(source.try_clone_to_owned()?))
+}
+
 /// Converts a cpdb-libs-allocated translation string into `Option<String>`,
 /// freeing the underlying buffer.
 ///
@@ -848,6 +2170,7 @@ unsafe fn translation_to_option(ptr: *mut c_char) -> Result<Option<String>> {
 impl Drop for Printer<'_> {
     fn drop(&mut self) {
         if self.owned {
+            runtime_settings().remove(&RuntimeKey::Object(self.raw.as_ptr() as usize));
             // SAFETY: we own the pointer and have not aliased it.
             unsafe { ffi::cpdbDeletePrinterObj(self.raw.as_ptr()) };
         }
@@ -855,8 +2178,9 @@ impl Drop for Printer<'_> {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
+    use crate::ipp::FinishingPosition;
 
     #[test]
     fn from_raw_borrowed_rejects_null() {
@@ -864,6 +2188,594 @@ mod tests {
         assert!(matches!(r, Err(CpdbError::NullPointer)));
     }
 
+    #[test]
+    fn printer_missing_from_frontend_table_is_invalid() {
+        // SAFETY: an all-zero frontend object is a valid (empty) value.
+        let mut frontend = unsafe { std::mem::zeroed::<ffi::cpdb_frontend_obj_t>() };
+        let stale =
+            Printer::from_frontend_table(NonNull::dangling().as_ptr(), &mut frontend).unwrap();
+        assert!(!stale.is_valid());
+        assert!(dangling_printer().is_valid());
+    }
+
+    /// A borrowed printer whose pointer must never be dereferenced. Only
+    /// for tests that fail before reaching cpdb-libs.
+    fn dangling_printer() -> Printer<'static> {
+        Printer::from_raw_borrowed(NonNull::dangling().as_ptr()).unwrap()
+    }
+
+    /// An all-zero printer object: every string field and the option
+    /// table are null, and no locale has been acquired. Borrow it with
+    /// [`Printer::from_raw_borrowed`].
+    pub(crate) fn zeroed_printer() -> ffi::cpdb_printer_obj_t {
+        // SAFETY: every field of the bindgen struct is a pointer or an
+        // integer, so all-zero is a valid value.
+        unsafe { std::mem::zeroed() }
+    }
+
+    #[test]
+    fn print_file_accounted_reports_missing_file_before_ffi() {
+        let p = dangling_printer();
+        let r = p.print_file_accounted("/tmp/cpdb-rs-nonexistent-print-file");
+        assert!(matches!(r, Err(CpdbError::IoError(_))));
+    }
+
+    #[test]
+    fn cached_accepting_jobs_reads_struct_field() {
+        let mut obj = zeroed_printer();
+        let p = Printer::from_raw_borrowed(&mut obj).unwrap();
+        assert!(!p.is_accepting_jobs_cached());
+        drop(p);
+
+        obj.accepting_jobs = 1;
+        let p = Printer::from_raw_borrowed(&mut obj).unwrap();
+        assert!(p.is_accepting_jobs_cached());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn runtime_setting_beats_saved_setting() {
+        crate::init();
+        // SAFETY: constructor with no preconditions; ownership passes to
+        // the `Printer`, which frees it on drop.
+        let raw = unsafe { ffi::cpdbGetNewPrinterObj() };
+        let printer = Printer::from_raw_owned(raw).expect("cpdbGetNewPrinterObj failed");
+        printer.add_setting("copies", "2").unwrap();
+
+        // cpdb-libs merging saved settings into the object after the
+        // runtime setting was made.
+        let mut saved = Settings::new().unwrap();
+        saved.add_setting("copies", "5").unwrap();
+        saved.add_setting("sides", "two-sided-long-edge").unwrap();
+        // SAFETY: both settings tables are live for the call.
+        unsafe { ffi::cpdbCopySettings(saved.as_raw(), (*raw).settings) };
+
+        assert_eq!(printer.get_current("copies").unwrap(), "2");
+        assert_eq!(printer.view().get_current("copies").unwrap(), "2");
+        assert_eq!(printer.get_current("sides").unwrap(), "two-sided-long-edge");
+
+        printer.clear_setting("copies").unwrap();
+        // SAFETY: as above.
+        unsafe { ffi::cpdbCopySettings(saved.as_raw(), (*raw).settings) };
+        assert_eq!(printer.get_current("copies").unwrap(), "5");
+    }
+
+    #[test]
+    fn runtime_settings_follow_the_queue_not_the_handle() {
+        // SAFETY: an all-zero frontend object is a valid (empty) value.
+        let mut frontend = unsafe { std::mem::zeroed::<ffi::cpdb_frontend_obj_t>() };
+        let mut obj = zeroed_printer();
+        let first = Printer::from_frontend_table(&mut obj, &mut frontend).unwrap();
+        first.record_runtime_setting("copies", Some("2"));
+
+        // A rediscovered printer is a new object for the same queue.
+        let mut replacement = zeroed_printer();
+        let second = Printer::from_frontend_table(&mut replacement, &mut frontend).unwrap();
+        assert_eq!(second.runtime_setting("copies").as_deref(), Some("2"));
+
+        forget_runtime_settings(&mut frontend);
+        assert_eq!(second.runtime_setting("copies"), None);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn settings_overlay_restores_previous_values() {
+        crate::init();
+        // SAFETY: constructor with no preconditions; ownership passes to
+        // the `Printer`, which frees it on drop.
+        let raw = unsafe { ffi::cpdbGetNewPrinterObj() };
+        let printer = Printer::from_raw_owned(raw).expect("cpdbGetNewPrinterObj failed");
+        printer.add_setting("copies", "2").unwrap();
+
+        let overlay = SettingsOverlay::apply(
+            &printer,
+            vec![
+                ("copies".into(), "5".into()),
+                ("sides".into(), "two-sided-long-edge".into()),
+            ],
+        )
+        .unwrap();
+        assert_eq!(printer.get_current("copies").unwrap(), "5");
+        drop(overlay);
+
+        assert_eq!(printer.get_current("copies").unwrap(), "2");
+        assert_eq!(printer.get_setting("sides").unwrap(), None);
+    }
+
+    fn option(name: &str, supported: &[&str]) -> OptionInfo {
+        OptionInfo {
+            name: name.into(),
+            default_value: supported.first().copied().unwrap_or_default().into(),
+            group: String::new(),
+            supported_values: supported.iter().map(|v| v.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn check_choice_accepts_supported_bin() {
+        let bins = option("output-bin", &["face-down", "stacker-1", "stacker-2"]);
+        assert!(check_choice("output-bin", "stacker-2", Some(&bins)).is_ok());
+    }
+
+    #[test]
+    fn check_choice_rejects_unknown_bin() {
+        let bins = option("output-bin", &["face-down", "stacker-1"]);
+        let r = check_choice("output-bin", "stacker-9", Some(&bins));
+        match r {
+            Err(CpdbError::OptionError(msg)) => {
+                assert!(msg.contains("stacker-9"));
+                assert!(msg.contains("face-down, stacker-1"));
+            }
+            other => panic!("expected OptionError, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn check_choice_rejects_missing_option() {
+        let r = check_choice("output-bin", "face-down", None);
+        assert!(matches!(r, Err(CpdbError::OptionError(_))));
+    }
+
+    #[test]
+    fn media_type_accepts_advertised_stock() {
+        let stock = option(
+            "media-type",
+            &["stationery", "photographic-glossy", "transparency"],
+        );
+        assert!(check_choice("media-type", "photographic-glossy", Some(&stock)).is_ok());
+        match check_choice("media-type", "cardstock", Some(&stock)) {
+            Err(CpdbError::OptionError(msg)) => {
+                assert!(msg.contains("cardstock"));
+                assert!(msg.contains("stationery, photographic-glossy, transparency"));
+            }
+            other => panic!("expected OptionError, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn media_type_needs_the_option_exposed() {
+        match check_choice("media-type", "photographic-glossy", None) {
+            Err(CpdbError::OptionError(msg)) => assert!(msg.contains("media-type")),
+            other => panic!("expected OptionError, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn simplex_only_printer_rejects_duplex() {
+        let sides = option("sides", &["one-sided"]);
+        let r = resolve_sides(Sides::TwoSidedLongEdge, Some(&sides), false);
+        match r {
+            Err(CpdbError::OptionError(msg)) => {
+                assert!(msg.contains("two-sided-long-edge"));
+                assert!(msg.contains("supported: one-sided"));
+            }
+            other => panic!("expected OptionError, got {other:?}"),
+        }
+        // A printer without the option at all behaves the same way.
+        assert!(resolve_sides(Sides::TwoSidedShortEdge, None, false).is_err());
+    }
+
+    #[test]
+    fn sides_fallback_resolves_to_one_sided() {
+        let sides = option("sides", &["one-sided"]);
+        let r = resolve_sides(Sides::TwoSidedLongEdge, Some(&sides), true);
+        assert_eq!(r.unwrap(), Sides::OneSided);
+
+        let duplex = option("sides", &["one-sided", "two-sided-long-edge"]);
+        let r = resolve_sides(Sides::TwoSidedLongEdge, Some(&duplex), true);
+        assert_eq!(r.unwrap(), Sides::TwoSidedLongEdge);
+    }
+
+    #[test]
+    fn finishings_col_preferred_when_supported() {
+        let col = FinishingsCol::new(FinishingType::Staple, FinishingPosition::TopLeft);
+        let col_info = option("finishings-col", &["{finishing-template=staple-top-left}"]);
+        let plain = option("finishings", &["none", "staple-top-left"]);
+        let (opt, value) = resolve_finishings(col, Some(&col_info), Some(&plain)).unwrap();
+        assert_eq!(opt, "finishings-col");
+        assert_eq!(value, "{finishing-template=staple-top-left}");
+    }
+
+    #[test]
+    fn finishings_falls_back_to_plain_keyword() {
+        let col = FinishingsCol::new(FinishingType::Staple, FinishingPosition::BottomRight);
+        let col_info = option("finishings-col", &["staple-top-left"]);
+        let plain = option("finishings", &["none", "staple-bottom-right"]);
+        let (opt, value) = resolve_finishings(col, Some(&col_info), Some(&plain)).unwrap();
+        assert_eq!(opt, "finishings");
+        assert_eq!(value, "staple-bottom-right");
+    }
+
+    #[test]
+    fn finishings_rejects_unsupported_position() {
+        let col = FinishingsCol::new(FinishingType::Staple, FinishingPosition::DualTop);
+        let plain = option("finishings", &["none", "staple-top-left"]);
+        let r = resolve_finishings(col, None, Some(&plain));
+        assert!(matches!(r, Err(CpdbError::OptionError(_))));
+    }
+
+    #[test]
+    fn bundled_test_page_is_one_page_postscript() {
+        let page = std::str::from_utf8(TEST_PAGE_PS).unwrap();
+        assert!(page.starts_with("%!PS-Adobe-3.0"));
+        assert!(page.contains("%%Pages: 1"));
+        assert_eq!(page.matches("showpage").count(), 1);
+        assert!(page.trim_end().ends_with("%%EOF"));
+    }
+
+    #[test]
+    fn account_id_with_nul_is_rejected_before_ffi() {
+        let p = dangling_printer();
+        let r = p.set_account_id("dept-42\0x");
+        assert!(matches!(r, Err(CpdbError::NulError(_))));
+    }
+
+    #[test]
+    fn required_account_id_must_be_present() {
+        assert!(check_account_id_present(Some("dept-42")).is_ok());
+        for missing in [None, Some(""), Some("  "), Some("NA")] {
+            assert!(matches!(
+                check_account_id_present(missing),
+                Err(CpdbError::OptionError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn account_id_in_job_options_satisfies_requirement() {
+        // The options carry the ID, so the printer is never consulted.
+        let p = dangling_printer().require_account_id();
+        assert!(p.check_account_id(&[("job-account-id", "dept-42")]).is_ok());
+        assert!(p.view().account_required);
+        assert!(matches!(
+            p.check_account_id(&[("job-account-id", "")]),
+            Err(CpdbError::OptionError(_))
+        ));
+        assert!(dangling_printer().check_account_id(&[]).is_ok());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn account_id_round_trips() {
+        crate::init();
+        // SAFETY: constructor with no preconditions; ownership passes to
+        // the `Printer`, which frees it on drop.
+        let raw = unsafe { ffi::cpdbGetNewPrinterObj() };
+        let printer = Printer::from_raw_owned(raw).expect("cpdbGetNewPrinterObj failed");
+        assert_eq!(printer.account_id().unwrap(), None);
+        printer.set_account_id("dept-42").unwrap();
+        assert_eq!(printer.account_id().unwrap().as_deref(), Some("dept-42"));
+        assert_eq!(printer.get_current("job-account-id").unwrap(), "dept-42");
+    }
+
+    #[test]
+    fn open_queues_do_not_require_auth() {
+        assert!(!auth_required(&option(
+            "uri-authentication-supported",
+            &["none"]
+        )));
+        assert!(!auth_required(&option(
+            "uri-authentication-supported",
+            &["requesting-user-name"]
+        )));
+        assert!(!auth_required(&option("uri-authentication-supported", &[])));
+    }
+
+    #[test]
+    fn secured_queues_require_auth() {
+        assert!(auth_required(&option(
+            "uri-authentication-supported",
+            &["basic"]
+        )));
+        assert!(auth_required(&option(
+            "uri-authentication-supported",
+            &["none", "negotiate"]
+        )));
+    }
+
+    #[test]
+    fn check_identity_reports_unreadable_fields() {
+        let id = CString::new("office").unwrap();
+        let name = CString::new("Office").unwrap();
+        let backend = CString::new("CUPS").unwrap();
+        let bad = CString::new(vec![b'O', 0xff, b'x']).unwrap();
+
+        let mut obj = zeroed_printer();
+        let check = |obj: &mut ffi::cpdb_printer_obj_t| {
+            Printer::from_raw_borrowed(obj).unwrap().check_identity()
+        };
+        assert_eq!(check(&mut obj), Err("missing id".into()));
+
+        obj.id = id.as_ptr() as *mut c_char;
+        obj.name = bad.as_ptr() as *mut c_char;
+        obj.backend_name = backend.as_ptr() as *mut c_char;
+        assert_eq!(check(&mut obj), Err("name is not valid UTF-8".into()));
+
+        obj.name = name.as_ptr() as *mut c_char;
+        assert_eq!(check(&mut obj), Ok(()));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn get_all_options_is_empty_without_option_table() {
+        let mut obj = zeroed_printer();
+        let printer = Printer::from_raw_borrowed(&mut obj).unwrap();
+        assert!(printer.get_all_options().unwrap().is_empty());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn list_media_is_empty_without_option_table() {
+        let mut obj = zeroed_printer();
+        let printer = Printer::from_raw_borrowed(&mut obj).unwrap();
+        assert!(printer.list_media().unwrap().is_empty());
+    }
+
+    #[test]
+    fn media_info_decodes_size_and_margins() {
+        let name = CString::new("iso_a4_210x297mm").unwrap();
+        let mut margins = [
+            ffi::cpdb_margin_t {
+                left: 0,
+                right: 0,
+                top: 0,
+                bottom: 0,
+            },
+            ffi::cpdb_margin_t {
+                left: 300,
+                right: 300,
+                top: 420,
+                bottom: 420,
+            },
+        ];
+        let mut raw = ffi::cpdb_media_t {
+            name: name.as_ptr() as *mut c_char,
+            width: 21000,
+            length: 29700,
+            num_margins: margins.len() as i32,
+            margins: margins.as_mut_ptr(),
+        };
+
+        // SAFETY: `raw` and the arrays it points to are live locals.
+        let info = unsafe { media_info_from_raw(&raw) }.unwrap();
+        assert_eq!(info.name, "iso_a4_210x297mm");
+        assert_eq!(
+            info.size(),
+            MediaSize {
+                width: 21000,
+                length: 29700
+            }
+        );
+        assert_eq!(info.margins.len(), 2);
+        assert_eq!(
+            info.margins[1],
+            Margin {
+                top: 420,
+                bottom: 420,
+                left: 300,
+                right: 300
+            }
+        );
+
+        raw.margins = std::ptr::null_mut();
+        // SAFETY: as above; a null margin array decodes as no margins.
+        assert!(
+            unsafe { media_info_from_raw(&raw) }
+                .unwrap()
+                .margins
+                .is_empty()
+        );
+        raw.name = std::ptr::null_mut();
+        // SAFETY: as above; a null name is rejected before any other field is read.
+        assert_eq!(unsafe { media_info_from_raw(&raw) }, None);
+    }
+
+    #[test]
+    fn cache_hit_does_not_populate_printer_translations() {
+        let model = CString::new("ACME LaserJet 9000").unwrap();
+        let mut obj = zeroed_printer();
+        obj.make_and_model = model.as_ptr() as *mut c_char;
+        let printer = Printer::from_raw_borrowed(&mut obj).unwrap();
+
+        let cache = TranslationCache::new(4);
+        let map = TranslationMap {
+            locale: Some("de_DE".into()),
+            entries: HashMap::from([("sides".to_owned(), "Beidseitig".to_owned())]),
+        };
+        cache.insert("ACME LaserJet 9000", "de_DE", map.clone());
+
+        let hit = printer.all_translations_cached("de_DE", &cache).unwrap();
+        assert_eq!(hit, map);
+        assert_eq!(hit.get("sides"), Some("Beidseitig"));
+        // The printer's own table was never filled.
+        assert_eq!(printer.option_translation("sides").unwrap(), "sides");
+    }
+
+    #[test]
+    fn translations_pass_names_through_without_a_locale() {
+        let mut obj = zeroed_printer();
+        let printer = Printer::from_raw_borrowed(&mut obj).unwrap();
+        assert_eq!(
+            printer.option_translation("no-such-option").unwrap(),
+            "no-such-option"
+        );
+        assert_eq!(
+            printer
+                .choice_translation("sides", "two-sided-long-edge")
+                .unwrap(),
+            "two-sided-long-edge"
+        );
+        assert_eq!(
+            printer.group_translation("Finishings").unwrap(),
+            "Finishings"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn duplicate_reader_leaves_source_open() {
+        use std::io::Write;
+
+        let (mut writer, source) = UnixStream::pair().unwrap();
+        writer.write_all(b"%PDF-1.7").unwrap();
+        drop(writer);
+
+        let mut data = Vec::new();
+        duplicate_reader(source.as_fd())
+            .unwrap()
+            .read_to_end(&mut data)
+            .unwrap();
+        assert_eq!(data, b"%PDF-1.7");
+        // The caller's descriptor is still open: reading now hits EOF
+        // rather than failing with EBADF.
+        assert_eq!((&source).read(&mut [0u8; 1]).unwrap(), 0);
+    }
+
+    #[test]
+    fn view_never_owns_the_printer() {
+        let mut obj = zeroed_printer();
+        let owner = Printer::from_raw_owned(&mut obj).unwrap();
+        let view = owner.view();
+        let nested = view.view();
+        assert!(!view.owned && !nested.owned);
+        assert_eq!(view.as_raw(), owner.as_raw());
+        // Dropping the views must not free the object.
+        drop(nested);
+        drop(view);
+        assert!(owner.owned);
+        // The object lives on the stack, so skip the owner's free.
+        std::mem::forget(owner);
+    }
+
+    #[test]
+    fn submit_job_rejects_interior_nul_before_ffi() {
+        let p = dangling_printer();
+        for options in [
+            [("copies", "1"), ("si\0des", "one-sided")],
+            [("copies", "1"), ("sides", "one\0sided")],
+        ] {
+            let r = p.submit_job("/tmp/doc.pdf", &options, "title");
+            assert!(matches!(r, Err(CpdbError::NulError(_))));
+        }
+    }
+
+    #[test]
+    fn raw_format_is_validated_against_supported_formats() {
+        let formats = option("document-format", &["application/pdf", RAW_DOCUMENT_FORMAT]);
+        assert_eq!(
+            raw_format_warning(RAW_DOCUMENT_FORMAT, Some(&formats)).unwrap(),
+            None
+        );
+        assert!(
+            raw_format_warning("application/vnd.hp-pcl", Some(&formats))
+                .unwrap()
+                .is_some()
+        );
+        // Matched like `accepts_pdf`: case-insensitively, ignoring parameters.
+        assert_eq!(
+            raw_format_warning("Application/PDF; version=1.7", Some(&formats)).unwrap(),
+            None
+        );
+        assert!(
+            raw_format_warning("application/pdf", None)
+                .unwrap()
+                .is_some()
+        );
+        for bad in ["", "pcl", "application/", "/pdf", "a/b/c"] {
+            assert!(matches!(
+                raw_format_warning(bad, Some(&formats)),
+                Err(CpdbError::OptionError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn pdf_support_comes_from_document_formats() {
+        assert!(lists_format(
+            &["application/postscript", "application/pdf"],
+            "application/pdf"
+        ));
+        assert!(!lists_format(
+            &["application/postscript"],
+            "application/pdf"
+        ));
+        assert!(lists_format(
+            &["Application/PDF; version=1.7"],
+            "application/pdf"
+        ));
+        assert!(!lists_format::<&str>(&[], "application/pdf"));
+    }
+
+    #[test]
+    fn available_choices_intersect_supported_with_installed() {
+        let supported =
+            || -> Vec<String> { ["tray-1", "tray-2", "tray-3"].map(String::from).to_vec() };
+        let ready = option("media-source-ready", &["tray-3", "tray-1"]);
+        assert_eq!(filter_available(supported(), &ready), ["tray-1", "tray-3"]);
+
+        let mut listed_as_default = option("media-source-installed", &[]);
+        listed_as_default.default_value = "tray-2, tray-9".into();
+        assert_eq!(
+            filter_available(supported(), &listed_as_default),
+            ["tray-2"]
+        );
+
+        assert_eq!(filter_available(Vec::new(), &ready), ["tray-3", "tray-1"]);
+    }
+
+    #[test]
+    fn default_copies_falls_back_to_one() {
+        assert_eq!(parse_default_copies(Some("3")), 3);
+        assert_eq!(parse_default_copies(Some("0")), 1);
+        assert_eq!(parse_default_copies(Some("NA")), 1);
+        assert_eq!(parse_default_copies(None), 1);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn typed_defaults_fall_back_without_options() {
+        // SAFETY: constructor with no preconditions; ownership passes to
+        // the `Printer`, which frees it on drop.
+        let raw = unsafe { ffi::cpdbGetNewPrinterObj() };
+        let printer = Printer::from_raw_owned(raw).expect("cpdbGetNewPrinterObj failed");
+        assert_eq!(printer.default_copies().unwrap(), 1);
+        assert_eq!(printer.default_sides().unwrap(), Sides::OneSided);
+        assert_eq!(
+            printer.default_orientation().unwrap(),
+            Orientation::Portrait
+        );
+    }
+
+    #[test]
+    fn up_time_converts_seconds() {
+        assert_eq!(parse_up_time("3600"), Some(Duration::from_secs(3600)));
+        assert_eq!(parse_up_time(" 42 "), Some(Duration::from_secs(42)));
+        assert_eq!(parse_up_time("0"), Some(Duration::ZERO));
+        assert_eq!(parse_up_time("NA"), None);
+        assert_eq!(parse_up_time("-5"), None);
+        assert_eq!(parse_up_time(""), None);
+    }
+
     #[test]
     fn from_raw_owned_rejects_null() {
         let r = Printer::from_raw_owned(std::ptr::null_mut());
@@ -2,7 +2,12 @@
 
 use crate::error::{CpdbError, Result};
 use crate::ffi;
-use std::ffi::CString;
+use crate::printer::{self, Margin, MediaSize};
+use crate::util;
+use crate::variant::GVariantRef;
+use libc::c_char;
+use std::ffi::{CStr, CString};
+use std::mem::MaybeUninit;
 use std::ptr::NonNull;
 
 // ─── Settings ────────────────────────────────────────────────────────────────
@@ -13,6 +18,7 @@ use std::ptr::NonNull;
 /// later applied. For per-printer settings see [`crate::Printer`].
 pub struct Settings {
     raw: NonNull<ffi::cpdb_settings_t>,
+    normalize_keys: bool,
 }
 
 // SAFETY: `Settings` owns its `cpdb_settings_t *`. Moving it across threads
@@ -28,13 +34,41 @@ impl Settings {
         // SAFETY: `cpdbGetNewSettings` is a constructor with no preconditions.
         let raw = unsafe { ffi::cpdbGetNewSettings() };
         NonNull::new(raw)
-            .map(|raw| Self { raw })
+            .map(Self::wrap)
             .ok_or_else(|| CpdbError::BackendError("cpdbGetNewSettings returned null".into()))
     }
 
-    /// Returns an independent deep copy.
+    fn wrap(raw: NonNull<ffi::cpdb_settings_t>) -> Self {
+        Self {
+            raw,
+            normalize_keys: false,
+        }
+    }
+
+    /// Enables mapping of PPD-era keys to IPP names on insertion and
+    /// removal (see [`util::normalize_option_key`]), so
+    /// `add_setting("Duplex", ...)` stores `sides`.
+    ///
+    /// Off by default: without it, keys are stored exactly as given.
+    pub fn with_key_normalization(mut self) -> Self {
+        self.normalize_keys = true;
+        self
+    }
+
+    /// Applies key normalization when enabled.
+    fn key<'k>(&self, key: &'k str) -> &'k str {
+        if self.normalize_keys {
+            util::normalize_option_key(key)
+        } else {
+            key
+        }
+    }
+
+    /// Returns an independent deep copy, including the key-normalization
+    /// flag.
     pub fn try_clone(&self) -> Result<Self> {
-        let dst = Self::new()?;
+        let mut dst = Self::new()?;
+        dst.normalize_keys = self.normalize_keys;
         // SAFETY: both pointers are valid, distinct, and live for the duration
         // of this call.
         unsafe { ffi::cpdbCopySettings(self.raw.as_ptr(), dst.raw.as_ptr()) };
@@ -43,7 +77,7 @@ impl Settings {
 
     /// Inserts or overwrites a setting.
     pub fn add_setting(&mut self, key: &str, value: &str) -> Result<()> {
-        let key = CString::new(key)?;
+        let key = CString::new(self.key(key))?;
         let value = CString::new(value)?;
         // SAFETY: pointer is non-null; the two `CString`s outlive the call.
         unsafe { ffi::cpdbAddSetting(self.raw.as_ptr(), key.as_ptr(), value.as_ptr()) };
@@ -54,19 +88,73 @@ impl Settings {
     ///
     /// Returns `Ok(true)` when the key existed before this call.
     pub fn clear_setting(&mut self, key: &str) -> Result<bool> {
-        let key = CString::new(key)?;
+        let key = CString::new(self.key(key))?;
         // SAFETY: pointer is non-null; `CString` outlives the call.
         let existed = unsafe { ffi::cpdbClearSetting(self.raw.as_ptr(), key.as_ptr()) };
         Ok(existed != 0)
     }
 
+    /// Returns the value stored under `key`, or `None` when it is absent.
+    ///
+    /// The key is normalized first when
+    /// [`Settings::with_key_normalization`] is enabled.
+    pub fn get(&self, key: &str) -> Result<Option<String>> {
+        let key = CString::new(self.key(key))?;
+        // cpdb-libs has no settings-level getter: `cpdbGetSetting` takes a
+        // `cpdb_printer_obj_t` and reads that printer's own table, so a
+        // standalone collection is looked up in its GLib table directly —
+        // the same lookup `cpdbGetSetting` performs internally.
+        // SAFETY: pointer is non-null; reading the `table` field is sound.
+        let table = unsafe { (*self.raw.as_ptr()).table } as *mut glib_sys::GHashTable;
+        if table.is_null() {
+            return Ok(None);
+        }
+        // SAFETY: the table maps NUL-terminated keys to NUL-terminated
+        // values it owns; the value is copied before returning.
+        unsafe {
+            let value =
+                glib_sys::g_hash_table_lookup(table, key.as_ptr() as glib_sys::gconstpointer);
+            if value.is_null() {
+                return Ok(None);
+            }
+            util::cstr_to_string(value as *const c_char).map(Some)
+        }
+    }
+
+    /// Iterates over copies of every stored `(key, value)` pair, in the
+    /// table's (unspecified) order.
+    ///
+    /// The pairs are copied up front, so the iterator does not borrow the
+    /// underlying table.
+    pub fn iter(&self) -> impl Iterator<Item = (String, String)> + use<> {
+        self.entries().into_iter()
+    }
+
     /// Persists this settings collection to the cpdb-managed config dir.
     ///
     /// The path is chosen by cpdb-libs (see `cpdbGetUserConfDir`) — callers
     /// cannot override it.
+    ///
+    /// `cpdbSaveSettingsToDisk` reports nothing, so the file is read back
+    /// with [`Settings::read_from_disk`] and compared entry by entry; a
+    /// missing or different file fails with [`CpdbError::BackendError`].
     pub fn save_to_disk(&self) -> Result<()> {
         // SAFETY: pointer is non-null.
         unsafe { ffi::cpdbSaveSettingsToDisk(self.raw.as_ptr()) };
+
+        let mut written = Self::read_from_disk()
+            .map_err(|_| CpdbError::BackendError("settings file was not written".into()))?
+            .entries();
+        let mut expected = self.entries();
+        written.sort();
+        expected.sort();
+        if written != expected {
+            return Err(CpdbError::BackendError(format!(
+                "settings file holds {} entries after saving {}",
+                written.len(),
+                expected.len()
+            )));
+        }
         Ok(())
     }
 
@@ -75,10 +163,80 @@ impl Settings {
         // SAFETY: `cpdbReadSettingsFromDisk` has no preconditions.
         let raw = unsafe { ffi::cpdbReadSettingsFromDisk() };
         NonNull::new(raw)
-            .map(|raw| Self { raw })
+            .map(Self::wrap)
             .ok_or_else(|| CpdbError::BackendError("cpdbReadSettingsFromDisk returned null".into()))
     }
 
+    /// Serialises the settings the way cpdb-libs sends them over D-Bus.
+    ///
+    /// The variant has type `a(ss)` — an array of `(name, value)` pairs.
+    /// An empty collection serialises as the single sentinel pair
+    /// `("NA", "NA")`.
+    pub fn to_gvariant(&self) -> Result<GVariantRef> {
+        // SAFETY: pointer is non-null; cpdb-libs returns a new floating
+        // variant, which `from_raw_full` sinks and owns.
+        unsafe {
+            let raw = ffi::cpdbSerializeToGVariant(self.raw.as_ptr());
+            GVariantRef::from_raw_full(raw as *mut glib_sys::GVariant)
+        }
+    }
+
+    /// [`Settings::to_gvariant`] in GVariant wire format, for passing
+    /// settings over a channel other than D-Bus.
+    pub fn to_gvariant_bytes(&self) -> Result<Vec<u8>> {
+        Ok(self.to_gvariant()?.to_bytes())
+    }
+
+    /// Rebuilds a settings collection from [`Settings::to_gvariant_bytes`]
+    /// output.
+    ///
+    /// Fails with [`CpdbError::OptionError`] when `bytes` are not a
+    /// serialised `a(ss)` array. The `("NA", "NA")` sentinel written for an
+    /// empty collection yields an empty collection again.
+    pub fn from_gvariant_bytes(bytes: &[u8]) -> Result<Self> {
+        let variant = GVariantRef::from_bytes("a(ss)", bytes)?;
+        let mut settings = Self::new()?;
+        for (key, value) in variant.string_pairs()? {
+            if key == "NA" && value == "NA" {
+                continue;
+            }
+            settings.add_setting(&key, &value)?;
+        }
+        Ok(settings)
+    }
+
+    /// Copies every key/value pair out of the settings table, in the
+    /// table's (unspecified) iteration order.
+    pub(crate) fn entries(&self) -> Vec<(String, String)> {
+        // SAFETY: pointer is non-null; reading the `table` field is sound.
+        let table = unsafe { (*self.raw.as_ptr()).table } as *mut glib_sys::GHashTable;
+        if table.is_null() {
+            return Vec::new();
+        }
+
+        let mut entries = Vec::new();
+        // SAFETY: iterator is initialised on the stack and iterated
+        // synchronously; keys and values are NUL-terminated strings owned
+        // by the table, copied before returning.
+        unsafe {
+            let mut iter = MaybeUninit::<glib_sys::GHashTableIter>::uninit();
+            glib_sys::g_hash_table_iter_init(iter.as_mut_ptr(), table);
+            let mut iter = iter.assume_init();
+
+            let mut key: glib_sys::gpointer = std::ptr::null_mut();
+            let mut value: glib_sys::gpointer = std::ptr::null_mut();
+            while glib_sys::g_hash_table_iter_next(&mut iter, &mut key, &mut value) != 0 {
+                if key.is_null() || value.is_null() {
+                    continue;
+                }
+                let k = CStr::from_ptr(key as *const c_char).to_string_lossy();
+                let v = CStr::from_ptr(value as *const c_char).to_string_lossy();
+                entries.push((k.into_owned(), v.into_owned()));
+            }
+        }
+        entries
+    }
+
     /// Returns the underlying raw pointer for use within this crate.
     #[doc(hidden)]
     pub fn as_raw(&self) -> *mut ffi::cpdb_settings_t {
@@ -161,6 +319,32 @@ impl Media {
             .ok_or(CpdbError::NullPointer)
     }
 
+    /// The PWG media name, e.g. `iso_a4_210x297mm`.
+    pub fn name(&self) -> Result<String> {
+        // SAFETY: pointer is non-null; reading the `name` field is sound.
+        unsafe { util::cstr_to_string((*self.raw.as_ptr()).name) }
+    }
+
+    /// The media's dimensions, in hundredths of a millimetre.
+    pub fn size(&self) -> MediaSize {
+        // SAFETY: pointer is non-null and the struct is initialised.
+        let media = unsafe { self.raw.as_ref() };
+        MediaSize {
+            width: media.width,
+            length: media.length,
+        }
+    }
+
+    /// Every margin set recorded for this media.
+    pub fn margins(&self) -> Vec<Margin> {
+        // SAFETY: `from_raw`'s contract makes the struct well-formed, so
+        // `margins` holds `num_margins` entries.
+        unsafe {
+            let media = self.raw.as_ref();
+            printer::margins_from_raw(media.margins, media.num_margins)
+        }
+    }
+
     /// Returns the underlying raw pointer for use within this crate.
     #[doc(hidden)]
     pub fn as_raw(&self) -> *mut ffi::cpdb_media_t {
@@ -0,0 +1,216 @@
+//! Serializable snapshots of printers and whole fleets (`serde` feature).
+//!
+//! A [`FleetSnapshot`] records every known printer and its capabilities as
+//! plain data, so it can be written to disk with any serde format and
+//! compared against a later snapshot with [`FleetSnapshot::diff`] — e.g. by
+//! a nightly audit job.
+//!
+//! Capabilities are only as complete as each printer's option table: call
+//! [`Printer::acquire_details`] on the printers first if the snapshot
+//! should include their full option sets.
+
+use crate::error::Result;
+use crate::options::OptionInfo;
+use crate::printer::Printer;
+use serde::{Deserialize, Serialize};
+
+/// The identifying fields and capabilities of one printer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrinterSnapshot {
+    /// The backend-assigned printer id.
+    pub id: String,
+    /// The backend the printer belongs to.
+    pub backend_name: String,
+    /// The human-readable name.
+    pub name: String,
+    /// The location string; empty when unset.
+    pub location: String,
+    /// The free-form description; empty when unset.
+    pub description: String,
+    /// The make and model; empty when unset.
+    pub make_and_model: String,
+    /// The cached state string.
+    pub state: String,
+    /// The cached accepting-jobs flag.
+    pub accepting_jobs: bool,
+    /// Every option in the printer's table, sorted by name.
+    pub options: Vec<OptionInfo>,
+}
+
+impl PrinterSnapshot {
+    /// Copies a printer's fields and options.
+    ///
+    /// Options come from [`Printer::get_all_options`], so cpdb-libs may
+    /// query the backend for them when the printer has no option table
+    /// yet. A printer whose table is still missing is captured with no
+    /// options rather than failing. Fails only when the id or backend name
+    /// is not valid UTF-8, since the printer could not be matched across
+    /// snapshots; a null id or backend name reads as empty.
+    pub fn capture(printer: &Printer<'_>) -> Result<Self> {
+        let options = printer.get_all_options()?;
+        Ok(Self {
+            id: printer.id()?,
+            backend_name: printer.backend_name()?,
+            name: printer.name().unwrap_or_default(),
+            location: printer.location().unwrap_or_default(),
+            description: printer.description().unwrap_or_default(),
+            make_and_model: printer.make_and_model().unwrap_or_default(),
+            state: printer.cached_state().unwrap_or_default(),
+            accepting_jobs: printer.is_accepting_jobs_cached(),
+            options,
+        })
+    }
+
+    /// The `(backend_name, id)` pair that identifies the printer across
+    /// snapshots.
+    pub fn key(&self) -> (&str, &str) {
+        (&self.backend_name, &self.id)
+    }
+}
+
+/// Snapshots of every printer a frontend knew about at one moment.
+///
+/// Built by [`Frontend::snapshot_fleet`](crate::Frontend::snapshot_fleet).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FleetSnapshot {
+    /// The printers, sorted by backend name and then id.
+    pub printers: Vec<PrinterSnapshot>,
+}
+
+impl FleetSnapshot {
+    /// Builds a snapshot from individual printer snapshots, sorting them.
+    pub fn from_printers(mut printers: Vec<PrinterSnapshot>) -> Self {
+        printers.sort_by(|a, b| a.key().cmp(&b.key()));
+        Self { printers }
+    }
+
+    /// Compares `self` (the older snapshot) with `other` (the newer one).
+    ///
+    /// Printers are matched by backend name and id. A matched printer is
+    /// reported as changed when any other field or option differs.
+    pub fn diff(&self, other: &FleetSnapshot) -> FleetDiff {
+        let mut diff = FleetDiff::default();
+        for before in &self.printers {
+            match other.find(before.key()) {
+                None => diff.removed.push(before.clone()),
+                Some(after) if after != before => diff.changed.push(PrinterChange {
+                    before: before.clone(),
+                    after: after.clone(),
+                }),
+                Some(_) => {}
+            }
+        }
+        for after in &other.printers {
+            if self.find(after.key()).is_none() {
+                diff.added.push(after.clone());
+            }
+        }
+        diff
+    }
+
+    fn find(&self, key: (&str, &str)) -> Option<&PrinterSnapshot> {
+        self.printers.iter().find(|p| p.key() == key)
+    }
+}
+
+/// The differences between two [`FleetSnapshot`]s.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FleetDiff {
+    /// Printers only in the newer snapshot.
+    pub added: Vec<PrinterSnapshot>,
+    /// Printers only in the older snapshot.
+    pub removed: Vec<PrinterSnapshot>,
+    /// Printers in both whose fields or options differ.
+    pub changed: Vec<PrinterChange>,
+}
+
+impl FleetDiff {
+    /// `true` when the two snapshots describe the same fleet.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// One printer as it appeared in each of two snapshots.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrinterChange {
+    /// The printer in the older snapshot.
+    pub before: PrinterSnapshot,
+    /// The printer in the newer snapshot.
+    pub after: PrinterSnapshot,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::printer::tests::zeroed_printer;
+
+    fn printer(backend: &str, id: &str, state: &str) -> PrinterSnapshot {
+        PrinterSnapshot {
+            id: id.into(),
+            backend_name: backend.into(),
+            name: id.into(),
+            location: String::new(),
+            description: String::new(),
+            make_and_model: "Acme LaserJet".into(),
+            state: state.into(),
+            accepting_jobs: true,
+            options: vec![OptionInfo {
+                name: "sides".into(),
+                default_value: "one-sided".into(),
+                group: String::new(),
+                supported_values: vec!["one-sided".into()],
+            }],
+        }
+    }
+
+    #[test]
+    fn from_printers_sorts_by_backend_then_id() {
+        let fleet = FleetSnapshot::from_printers(vec![
+            printer("FILE", "a", "idle"),
+            printer("CUPS", "b", "idle"),
+            printer("CUPS", "a", "idle"),
+        ]);
+        let keys: Vec<_> = fleet.printers.iter().map(|p| p.key()).collect();
+        assert_eq!(keys, [("CUPS", "a"), ("CUPS", "b"), ("FILE", "a")]);
+    }
+
+    #[test]
+    fn diff_reports_added_removed_and_changed() {
+        let old = FleetSnapshot::from_printers(vec![
+            printer("CUPS", "kept", "idle"),
+            printer("CUPS", "gone", "idle"),
+            printer("CUPS", "busy", "idle"),
+        ]);
+        let mut new = FleetSnapshot::from_printers(vec![
+            printer("CUPS", "kept", "idle"),
+            printer("CUPS", "busy", "processing"),
+            printer("FILE", "new", "idle"),
+        ]);
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].id, "new");
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].id, "gone");
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].before.state, "idle");
+        assert_eq!(diff.changed[0].after.state, "processing");
+
+        new.printers[1].options[0]
+            .supported_values
+            .push("two-sided-long-edge".into());
+        assert_eq!(old.diff(&new).changed.len(), 2);
+        assert!(old.diff(&old).is_empty());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn capture_keeps_a_printer_without_option_table() {
+        let mut obj = zeroed_printer();
+        let printer = Printer::from_raw_borrowed(&mut obj).unwrap();
+        let snapshot = PrinterSnapshot::capture(&printer).unwrap();
+        assert_eq!(snapshot.key(), ("", ""));
+        assert!(snapshot.options.is_empty());
+    }
+}
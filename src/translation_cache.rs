@@ -0,0 +1,160 @@
+//! A bounded cache of translation tables shared between printers of the
+//! same model.
+//!
+//! Fetching translations costs a D-Bus round trip per printer. In a dialog
+//! listing many identical printers that work is repeated for tables that
+//! are, in practice, the same. [`TranslationCache`] keys tables by
+//! `(make_and_model, locale)` so the first printer of a model pays for the
+//! fetch and the rest are served from memory via
+//! [`Printer::all_translations_cached`].
+//!
+//! # Assumption
+//!
+//! The cache assumes printers reporting the same make and model expose
+//! the same option vocabulary. That holds for identical hardware behind
+//! the same backend, but not necessarily for one model served by drivers
+//! of different versions; use a separate cache (or none) where that
+//! matters. Printers with an empty make-and-model are never cached.
+//!
+//! A hit hands back the cached map without touching the printer, so the
+//! printer's own translation table stays empty and per-printer getters
+//! such as [`Printer::option_translation`] return untranslated names.
+//! Read labels from the returned [`TranslationMap`] instead.
+//!
+//! [`Printer::all_translations_cached`]: crate::Printer::all_translations_cached
+//! [`Printer::option_translation`]: crate::Printer::option_translation
+
+use crate::printer::TranslationMap;
+use std::collections::VecDeque;
+use std::sync::{Mutex, MutexGuard};
+
+/// A bounded, thread-safe cache of [`TranslationMap`]s keyed by
+/// `(make_and_model, locale)`.
+///
+/// Opt-in: nothing is cached unless a cache is passed explicitly. Once
+/// `capacity` tables are held, inserting another evicts the least recently
+/// used one. Share a cache between threads behind an [`std::sync::Arc`].
+#[derive(Debug)]
+pub struct TranslationCache {
+    capacity: usize,
+    entries: Mutex<VecDeque<CacheEntry>>,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    make_and_model: String,
+    locale: String,
+    map: TranslationMap,
+}
+
+impl TranslationCache {
+    /// Creates an empty cache holding at most `capacity` tables. A
+    /// capacity of 0 disables caching.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// The maximum number of tables held.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of tables currently held.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// `true` when no tables are held.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Drops every cached table, e.g. after a driver update.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, VecDeque<CacheEntry>> {
+        self.entries.lock().unwrap_or_else(|p| p.into_inner())
+    }
+
+    /// Returns a copy of the cached table and marks it most recently used.
+    pub(crate) fn get(&self, make_and_model: &str, locale: &str) -> Option<TranslationMap> {
+        let mut entries = self.lock();
+        let index = entries
+            .iter()
+            .position(|e| e.make_and_model == make_and_model && e.locale == locale)?;
+        let entry = entries.remove(index)?;
+        let map = entry.map.clone();
+        entries.push_back(entry);
+        Some(map)
+    }
+
+    /// Stores a table, replacing any previous one for the same key and
+    /// evicting the least recently used table when full.
+    pub(crate) fn insert(&self, make_and_model: &str, locale: &str, map: TranslationMap) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut entries = self.lock();
+        entries.retain(|e| !(e.make_and_model == make_and_model && e.locale == locale));
+        while entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(CacheEntry {
+            make_and_model: make_and_model.to_owned(),
+            locale: locale.to_owned(),
+            map,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map(entry: &str) -> TranslationMap {
+        TranslationMap {
+            locale: None,
+            entries: [(entry.to_owned(), entry.to_uppercase())].into(),
+        }
+    }
+
+    #[test]
+    fn keyed_by_model_and_locale() {
+        let cache = TranslationCache::new(4);
+        cache.insert("Acme 100", "de_DE", map("a"));
+        assert_eq!(cache.get("Acme 100", "de_DE"), Some(map("a")));
+        assert_eq!(cache.get("Acme 100", "fr_FR"), None);
+        assert_eq!(cache.get("Acme 200", "de_DE"), None);
+
+        cache.insert("Acme 100", "de_DE", map("b"));
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get("Acme 100", "de_DE"), Some(map("b")));
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let cache = TranslationCache::new(2);
+        cache.insert("one", "C", map("1"));
+        cache.insert("two", "C", map("2"));
+        // Touch "one" so "two" becomes the eviction candidate.
+        assert!(cache.get("one", "C").is_some());
+        cache.insert("three", "C", map("3"));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("one", "C").is_some());
+        assert!(cache.get("two", "C").is_none());
+        assert!(cache.get("three", "C").is_some());
+    }
+
+    #[test]
+    fn zero_capacity_caches_nothing() {
+        let cache = TranslationCache::new(0);
+        cache.insert("one", "C", map("1"));
+        assert!(cache.is_empty());
+    }
+}
@@ -4,6 +4,8 @@ use crate::error::{CpdbError, Result};
 use crate::ffi;
 use libc::c_char;
 use std::ffi::{CStr, CString};
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
 
 /// Converts a borrowed C string into an owned `String`.
 ///
@@ -44,6 +46,16 @@ pub unsafe fn cstr_to_string_and_g_free(ptr: *mut c_char) -> Result<String> {
     Ok(owned)
 }
 
+/// Converts a file path into the NUL-terminated form cpdb-libs expects.
+///
+/// The path's raw bytes are used, so non-UTF-8 paths pass through
+/// unchanged. A path containing a NUL byte fails with
+/// [`CpdbError::InvalidPath`], which names the path.
+pub fn path_to_cstring(path: &Path) -> Result<CString> {
+    CString::new(path.as_os_str().as_bytes())
+        .map_err(|_| CpdbError::InvalidPath(format!("{path:?}")))
+}
+
 /// A pinned, owned array of `cpdb_option_t` with backing `CString` storage.
 ///
 /// The strings cannot be reallocated after construction, so raw pointers
@@ -58,6 +70,12 @@ pub struct COptions {
 }
 
 impl COptions {
+    /// Returns a raw pointer to the underlying option array, for cpdb-libs
+    /// functions that only read a `const cpdb_option_t *`.
+    pub fn as_ptr(&self) -> *const ffi::cpdb_option_t {
+        self.options.as_ptr()
+    }
+
     /// Returns a raw mutable pointer to the underlying option array,
     /// suitable for passing to cpdb-libs functions that expect a
     /// `cpdb_option_t *` plus a length.
@@ -109,6 +127,35 @@ pub fn to_c_options(pairs: &[(&str, &str)]) -> Result<COptions> {
     })
 }
 
+/// PPD-era option names and their IPP equivalents.
+const PPD_KEY_ALIASES: &[(&str, &str)] = &[
+    ("PageSize", "media"),
+    ("PageRegion", "media"),
+    ("MediaType", "media-type"),
+    ("InputSlot", "media-source"),
+    ("Duplex", "sides"),
+    ("OutputBin", "output-bin"),
+    ("Resolution", "printer-resolution"),
+    ("ColorModel", "print-color-mode"),
+    ("cupsPrintQuality", "print-quality"),
+    ("Copies", "copies"),
+    ("Orientation", "orientation-requested"),
+];
+
+/// Maps a PPD-era option name to its IPP equivalent, e.g. `"Duplex"` to
+/// `"sides"` or `"PageSize"` to `"media"`.
+///
+/// Matching is ASCII case-insensitive. Names that are already IPP, or that
+/// have no known equivalent, are returned unchanged. Only keys are mapped:
+/// PPD *values* such as `DuplexNoTumble` still need translating by the
+/// caller.
+pub fn normalize_option_key(key: &str) -> &str {
+    PPD_KEY_ALIASES
+        .iter()
+        .find(|(ppd, _)| ppd.eq_ignore_ascii_case(key))
+        .map_or(key, |(_, ipp)| ipp)
+}
+
 #[cfg(test)]
 mod tests {
     //! Pure-Rust unit tests. These do not touch cpdb-libs and are safe
@@ -125,7 +172,7 @@ mod tests {
             // SAFETY: pointer arithmetic inside the boxed slice; pointers
             // captured at construction remain valid for the slice's
             // lifetime.
-            let entry = unsafe { &*opts.options.as_ptr().add(i) };
+            let entry = unsafe { &*opts.as_ptr().add(i) };
             let name = unsafe { CStr::from_ptr(entry.option_name) }
                 .to_string_lossy()
                 .into_owned();
@@ -204,4 +251,45 @@ mod tests {
         let echoed = read_back(&moved);
         assert_eq!(echoed, vec![("k".to_string(), "v".to_string())]);
     }
+
+    #[test]
+    fn path_with_embedded_nul_names_the_path() {
+        let err = path_to_cstring(Path::new("/tmp/bad\0name.pdf")).unwrap_err();
+        match err {
+            CpdbError::InvalidPath(shown) => assert!(shown.contains("/tmp/bad")),
+            other => panic!("expected InvalidPath, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn non_utf8_path_bytes_pass_through() {
+        use std::ffi::OsStr;
+        let raw = b"/tmp/caf\xe9.pdf";
+        let c = path_to_cstring(Path::new(OsStr::from_bytes(raw))).unwrap();
+        assert_eq!(c.as_bytes(), raw);
+    }
+
+    #[test]
+    fn normalize_option_key_maps_ppd_aliases() {
+        let cases = [
+            ("PageSize", "media"),
+            ("PageRegion", "media"),
+            ("pagesize", "media"),
+            ("MediaType", "media-type"),
+            ("InputSlot", "media-source"),
+            ("Duplex", "sides"),
+            ("OutputBin", "output-bin"),
+            ("Resolution", "printer-resolution"),
+            ("ColorModel", "print-color-mode"),
+            ("cupsPrintQuality", "print-quality"),
+            ("Orientation", "orientation-requested"),
+            // Already-IPP and unknown keys pass through untouched.
+            ("sides", "sides"),
+            ("media", "media"),
+            ("x-vendor-thing", "x-vendor-thing"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(normalize_option_key(input), expected, "input {input:?}");
+        }
+    }
 }
@@ -0,0 +1,194 @@
+//! A reference-counted handle to a GLib `GVariant`.
+//!
+//! cpdb-libs serialises settings to `GVariant` before sending them over
+//! D-Bus. [`GVariantRef`] lets Rust code hold on to such a value without
+//! managing the reference count by hand.
+
+use crate::error::{CpdbError, Result};
+use crate::util;
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::ptr::NonNull;
+
+/// An owned, strong reference to an immutable `GVariant`.
+///
+/// Cloning takes another reference (`g_variant_ref`); dropping releases
+/// one (`g_variant_unref`). `GVariant`s are immutable and their reference
+/// counts are atomic, so the handle is [`Send`] and [`Sync`].
+pub struct GVariantRef {
+    raw: NonNull<glib_sys::GVariant>,
+}
+
+// SAFETY: GVariant instances are immutable after construction and GLib
+// updates their reference counts atomically.
+unsafe impl Send for GVariantRef {}
+unsafe impl Sync for GVariantRef {}
+
+impl GVariantRef {
+    /// Takes ownership of a freshly built variant.
+    ///
+    /// A floating reference is sunk; a full reference is adopted as is.
+    ///
+    /// # Safety
+    /// `raw` must be null or a valid `GVariant` whose floating or full
+    /// reference the caller transfers to the returned handle.
+    pub unsafe fn from_raw_full(raw: *mut glib_sys::GVariant) -> Result<Self> {
+        let raw = NonNull::new(raw).ok_or(CpdbError::NullPointer)?;
+        // SAFETY: caller guarantees a valid variant; `g_variant_take_ref`
+        // converts a floating reference to a full one and is a no-op
+        // otherwise, so either way we end up owning exactly one reference.
+        unsafe { glib_sys::g_variant_take_ref(raw.as_ptr()) };
+        Ok(Self { raw })
+    }
+
+    /// Deserialises `bytes`, as produced by [`GVariantRef::to_bytes`], as a
+    /// value of type `type_string`.
+    ///
+    /// The bytes are copied and treated as untrusted. Fails with
+    /// [`CpdbError::OptionError`] when `type_string` is not a GVariant type
+    /// or the bytes are not the normal form of a value of that type, which
+    /// is what GLib's serialiser always emits.
+    pub fn from_bytes(type_string: &str, bytes: &[u8]) -> Result<Self> {
+        let c_type = CString::new(type_string)?;
+        // SAFETY: `c_type` is NUL-terminated and outlives the call.
+        if unsafe { glib_sys::g_variant_type_string_is_valid(c_type.as_ptr()) } == 0 {
+            return Err(CpdbError::OptionError(format!(
+                "'{type_string}' is not a GVariant type"
+            )));
+        }
+        // SAFETY: the type string was validated above; `g_bytes_new` copies
+        // `bytes`, and the variant takes its own reference on the copy, so
+        // both temporaries can be released straight away.
+        let variant = unsafe {
+            let ty = glib_sys::g_variant_type_new(c_type.as_ptr());
+            let data =
+                glib_sys::g_bytes_new(bytes.as_ptr() as glib_sys::gconstpointer, bytes.len());
+            let raw = glib_sys::g_variant_new_from_bytes(ty, data, glib_sys::GFALSE);
+            glib_sys::g_bytes_unref(data);
+            glib_sys::g_variant_type_free(ty);
+            Self::from_raw_full(raw)?
+        };
+        // SAFETY: pointer is valid while we hold our reference.
+        if unsafe { glib_sys::g_variant_is_normal_form(variant.as_ptr()) } == 0 {
+            return Err(CpdbError::OptionError(format!(
+                "{} bytes are not a serialised '{type_string}' value",
+                bytes.len()
+            )));
+        }
+        Ok(variant)
+    }
+
+    /// The variant's type string, e.g. `"a(ss)"`.
+    pub fn type_string(&self) -> String {
+        // SAFETY: the returned string is owned by the variant and valid
+        // while we hold our reference.
+        unsafe { CStr::from_ptr(glib_sys::g_variant_get_type_string(self.raw.as_ptr())) }
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// The number of children of a container variant (0 for scalars).
+    pub fn n_children(&self) -> usize {
+        // SAFETY: pointer is valid while we hold our reference.
+        let container = unsafe { glib_sys::g_variant_is_container(self.raw.as_ptr()) } != 0;
+        if !container {
+            return 0;
+        }
+        // SAFETY: as above; only called on containers.
+        unsafe { glib_sys::g_variant_n_children(self.raw.as_ptr()) }
+    }
+
+    /// The serialised bytes of the variant, in GVariant wire format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // SAFETY: pointer is valid; `g_variant_get_data` returns `size`
+        // bytes owned by the variant (or null when `size` is 0).
+        unsafe {
+            let size = glib_sys::g_variant_get_size(self.raw.as_ptr());
+            let data = glib_sys::g_variant_get_data(self.raw.as_ptr()) as *const u8;
+            if data.is_null() || size == 0 {
+                return Vec::new();
+            }
+            std::slice::from_raw_parts(data, size).to_vec()
+        }
+    }
+
+    /// The `(name, value)` pairs of an `a(ss)` variant, in order.
+    ///
+    /// Fails with [`CpdbError::OptionError`] for any other type.
+    pub(crate) fn string_pairs(&self) -> Result<Vec<(String, String)>> {
+        let type_string = self.type_string();
+        if type_string != "a(ss)" {
+            return Err(CpdbError::OptionError(format!(
+                "expected an 'a(ss)' variant, got '{type_string}'"
+            )));
+        }
+        Ok((0..self.n_children())
+            .map(|i| {
+                let pair = self.child(i);
+                (pair.child(0).string_value(), pair.child(1).string_value())
+            })
+            .collect())
+    }
+
+    /// Child `index` of a container; the caller checks the type and bounds.
+    fn child(&self, index: usize) -> Self {
+        // SAFETY: pointer is valid; `g_variant_get_child_value` returns a
+        // new full reference, which the handle adopts.
+        let raw = unsafe { glib_sys::g_variant_get_child_value(self.raw.as_ptr(), index) };
+        Self {
+            raw: NonNull::new(raw).expect("g_variant_get_child_value never returns null"),
+        }
+    }
+
+    /// The contents of a string-typed variant.
+    fn string_value(&self) -> String {
+        // SAFETY: only called on `s` children; the string is owned by the
+        // variant and copied before our reference is released.
+        unsafe {
+            CStr::from_ptr(glib_sys::g_variant_get_string(
+                self.raw.as_ptr(),
+                std::ptr::null_mut(),
+            ))
+        }
+        .to_string_lossy()
+        .into_owned()
+    }
+
+    /// The GVariant text format of the value, as `g_variant_print` renders
+    /// it without type annotations.
+    pub fn print(&self) -> Result<String> {
+        // SAFETY: `g_variant_print` returns a newly allocated string we own.
+        unsafe { util::cstr_to_string_and_g_free(glib_sys::g_variant_print(self.raw.as_ptr(), 0)) }
+    }
+
+    /// The raw pointer, still owned by this handle. Call `g_variant_ref`
+    /// on it to keep the value beyond the handle's lifetime.
+    pub fn as_ptr(&self) -> *mut glib_sys::GVariant {
+        self.raw.as_ptr()
+    }
+}
+
+impl Clone for GVariantRef {
+    fn clone(&self) -> Self {
+        // SAFETY: pointer is valid; the new reference is released by the
+        // clone's Drop.
+        unsafe { glib_sys::g_variant_ref(self.raw.as_ptr()) };
+        Self { raw: self.raw }
+    }
+}
+
+impl Drop for GVariantRef {
+    fn drop(&mut self) {
+        // SAFETY: we own exactly one reference.
+        unsafe { glib_sys::g_variant_unref(self.raw.as_ptr()) };
+    }
+}
+
+impl fmt::Debug for GVariantRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GVariantRef")
+            .field("type", &self.type_string())
+            .field("value", &self.print().unwrap_or_default())
+            .finish()
+    }
+}
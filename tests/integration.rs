@@ -2,9 +2,14 @@
 //! require a session D-Bus and at least one cpdb backend to be active.
 //! Run with `cargo test -- --ignored`.
 
-use cpdb_rs::Frontend;
+use cpdb_rs::{Frontend, Printer, PrinterEvent};
 use std::fs;
 use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
 
 fn write_temp_test_file(name: &str) -> std::path::PathBuf {
     let mut path = std::env::temp_dir();
@@ -14,6 +19,72 @@ fn write_temp_test_file(name: &str) -> std::path::PathBuf {
     path
 }
 
+/// Pickles the first discovered printer, after its options have had time
+/// to load, to a fresh temp file. `None` when no printer is available.
+fn pickle_first_printer(frontend: &Frontend, name: &str) -> Option<PathBuf> {
+    frontend.connect_to_dbus().expect("connect_to_dbus failed");
+    thread::sleep(Duration::from_secs(2));
+    let printers = frontend.get_printers().expect("get_printers failed");
+    let printer = printers.first()?;
+    printer.acquire_details();
+    thread::sleep(Duration::from_secs(2));
+
+    let path = std::env::temp_dir().join(name);
+    let _ = fs::remove_file(&path);
+    printer
+        .pickle_to_file(&path, frontend)
+        .expect("pickle_to_file failed");
+    Some(path)
+}
+
+#[test]
+#[ignore]
+fn loaded_printer_and_view_drop_cleanly() {
+    cpdb_rs::init();
+    let frontend = Frontend::new().expect("frontend init failed");
+    let Some(path) = pickle_first_printer(&frontend, "cpdb-rs-view.pickle") else {
+        return; // no printer in CI is fine
+    };
+
+    let loaded = Printer::load_from_file(&path).expect("load_from_file failed");
+    let view = loaded.view();
+    assert_eq!(view.id().unwrap(), loaded.id().unwrap());
+    // The view must not free the object: the owner is still usable after
+    // it is gone, and dropping the owner frees it exactly once. Run under
+    // ASAN or valgrind to catch a double free or leak.
+    drop(view);
+    assert!(!loaded.id().unwrap().is_empty());
+    drop(loaded);
+    let _ = fs::remove_file(&path);
+}
+
+#[test]
+#[ignore]
+fn loaded_printer_reports_the_pickled_options() {
+    cpdb_rs::init();
+    let frontend = Frontend::new().expect("frontend init failed");
+    let Some(path) = pickle_first_printer(&frontend, "cpdb-rs-options.pickle") else {
+        return; // no printer in CI is fine
+    };
+    let loaded = Printer::load_from_file(&path).expect("load_from_file failed");
+    let printers = frontend.get_printers().expect("get_printers failed");
+    let original = printers
+        .iter()
+        .find(|p| p.id().ok() == loaded.id().ok())
+        .expect("the pickled printer is still discovered");
+    let live = original.get_all_options().expect("get_all_options failed");
+
+    let options = loaded.get_all_options().expect("get_all_options failed");
+    assert_eq!(options.len(), live.len());
+    for (got, want) in options.iter().zip(&live) {
+        assert!(!got.name.is_empty());
+        assert_eq!(got.name, want.name);
+        assert_eq!(got.default_value, want.default_value, "{}", got.name);
+        assert_eq!(got.supported_values, want.supported_values, "{}", got.name);
+    }
+    let _ = fs::remove_file(&path);
+}
+
 #[test]
 #[ignore]
 fn printer_discovery() {
@@ -28,6 +99,73 @@ fn printer_discovery() {
     }
 }
 
+#[test]
+#[ignore]
+fn listed_media_margins_match_get_media_margins() {
+    cpdb_rs::init();
+    let frontend = Frontend::new().expect("frontend init failed");
+    frontend.connect_to_dbus().expect("connect_to_dbus failed");
+    thread::sleep(Duration::from_secs(2));
+    let printers = frontend.get_printers().expect("get_printers failed");
+    let Some(printer) = printers.first() else {
+        return; // no printer in CI is fine
+    };
+    printer.acquire_details();
+    thread::sleep(Duration::from_secs(2));
+
+    for media in printer.list_media().expect("list_media failed") {
+        match printer.get_media_margins(&media.name) {
+            Ok(margins) => assert_eq!(margins.entries, media.margins, "{}", media.name),
+            Err(_) => assert!(media.margins.is_empty(), "{}", media.name),
+        }
+    }
+}
+
+#[test]
+#[ignore]
+fn get_printers_matches_reported_count() {
+    cpdb_rs::init();
+    let frontend = Frontend::new().expect("frontend init failed");
+    frontend.connect_to_dbus().expect("connect_to_dbus failed");
+    thread::sleep(Duration::from_secs(2));
+    frontend.refresh_printers();
+    thread::sleep(Duration::from_millis(500));
+
+    let printers = frontend.get_printers().expect("get_printers failed");
+    // SAFETY: plain field read of a live frontend that no other thread
+    // touches during the test.
+    let reported = unsafe { (*frontend.as_raw()).num_printers };
+    assert_eq!(printers.len(), reported as usize);
+}
+
+#[test]
+#[ignore]
+fn printer_events_reach_handler() {
+    cpdb_rs::init();
+    let frontend = Frontend::new().expect("frontend init failed");
+    let added = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&added);
+    frontend.on_printer_event(move |event| {
+        if let PrinterEvent::Added(printer) = event {
+            assert!(!printer.id().unwrap_or_default().is_empty());
+            counter.fetch_add(1, Ordering::SeqCst);
+        }
+    });
+    frontend.connect_to_dbus().expect("connect_to_dbus failed");
+    // Pumps the main context, so printer-added signals reach the handler.
+    let printers = frontend
+        .discover_printers(Duration::from_secs(2))
+        .expect("discover_printers failed");
+    eprintln!(
+        "{} added events, {} printers",
+        added.load(Ordering::SeqCst),
+        printers.len()
+    );
+    if !printers.is_empty() {
+        assert!(added.load(Ordering::SeqCst) > 0);
+    }
+}
+
 #[test]
 #[ignore]
 fn job_submission_applies_options() {
@@ -5,7 +5,7 @@
 //! suite but are skipped under `cargo miri test`.
 
 use cpdb_rs::error::CpdbError;
-use cpdb_rs::{Settings, init, util, version};
+use cpdb_rs::{Frontend, SemVer, Settings, init, util, version};
 use std::ffi::CString;
 
 #[test]
@@ -24,18 +24,55 @@ fn version_is_non_empty_when_present() {
     }
 }
 
+#[test]
+fn semver_parses_release_formats() {
+    let v = SemVer::parse("2.0.1").unwrap();
+    assert_eq!((v.major, v.minor, v.patch, v.pre), (2, 0, 1, None));
+
+    let beta = SemVer::parse("2.0b4").unwrap();
+    assert_eq!((beta.major, beta.minor, beta.patch), (2, 0, 0));
+    assert_eq!(beta.pre.as_deref(), Some("b4"));
+    assert!(beta.at_least(2, 0, 0));
+    assert!(!beta.at_least(2, 1, 0));
+
+    let rc = SemVer::parse("v2.1.0-rc1").unwrap();
+    assert_eq!(rc.to_string(), "2.1.0-rc1");
+    assert_eq!(SemVer::parse("1.2").unwrap().to_string(), "1.2.0");
+}
+
+#[test]
+fn semver_rejects_dev_strings() {
+    for dev in ["UNKNOWN", "", "3f9c2ab", "2", "2.x", "2.0.0.1", "2.0-"] {
+        assert_eq!(SemVer::parse(dev), None, "{dev:?} should not parse");
+    }
+}
+
 #[test]
 #[cfg_attr(miri, ignore)]
 fn settings_lifecycle() {
     init();
     let mut s = Settings::new().expect("Settings::new failed");
     s.add_setting("copies", "1").unwrap();
+    assert_eq!(s.get("copies").unwrap().as_deref(), Some("1"));
+    assert_eq!(
+        s.iter().collect::<Vec<_>>(),
+        [("copies".to_string(), "1".to_string())]
+    );
     let existed = s.clear_setting("copies").unwrap();
     assert!(existed, "the key we just inserted should have existed");
     let again = s.clear_setting("copies").unwrap();
     assert!(!again, "clearing a missing key should return false");
 }
 
+#[test]
+#[cfg_attr(miri, ignore)]
+fn settings_get_on_empty_collection_is_none() {
+    init();
+    let s = Settings::new().expect("Settings::new failed");
+    assert_eq!(s.get("copies").unwrap(), None);
+    assert_eq!(s.iter().count(), 0);
+}
+
 #[test]
 #[cfg_attr(miri, ignore)]
 fn settings_try_clone_is_independent() {
@@ -49,6 +86,100 @@ fn settings_try_clone_is_independent() {
     let _ = a.clear_setting("media").unwrap();
 }
 
+#[test]
+#[cfg_attr(miri, ignore)]
+fn settings_serialize_to_string_pair_array() {
+    init();
+    let mut s = Settings::new().expect("Settings::new failed");
+    s.add_setting("copies", "2").unwrap();
+    let v = s.to_gvariant().expect("to_gvariant failed");
+    assert_eq!(v.type_string(), "a(ss)");
+    assert_eq!(v.n_children(), 1);
+    let copy = v.clone();
+    drop(v);
+    assert_eq!(copy.print().unwrap(), "[('copies', '2')]");
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn settings_round_trip_through_gvariant_bytes() {
+    init();
+    let mut s = Settings::new().expect("Settings::new failed");
+    s.add_setting("copies", "2").unwrap();
+    s.add_setting("sides", "two-sided-long-edge").unwrap();
+    s.add_setting("media", "iso_a4_210x297mm").unwrap();
+
+    let bytes = s.to_gvariant_bytes().expect("to_gvariant_bytes failed");
+    let back = Settings::from_gvariant_bytes(&bytes).expect("from_gvariant_bytes failed");
+    for key in ["copies", "sides", "media"] {
+        assert_eq!(back.get(key).unwrap(), s.get(key).unwrap(), "{key}");
+    }
+    assert_eq!(back.iter().count(), 3);
+
+    let empty = Settings::new().unwrap().to_gvariant_bytes().unwrap();
+    assert_eq!(
+        Settings::from_gvariant_bytes(&empty)
+            .unwrap()
+            .iter()
+            .count(),
+        0
+    );
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn settings_from_gvariant_bytes_rejects_garbage() {
+    init();
+    assert!(matches!(
+        Settings::from_gvariant_bytes(b"not a variant"),
+        Err(cpdb_rs::CpdbError::OptionError(_))
+    ));
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn parallel_frontends_get_distinct_instance_names() {
+    init();
+    let a = Frontend::new().expect("first frontend");
+    let b = Frontend::new().expect("second frontend");
+    assert_ne!(a.instance_name(), b.instance_name());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn explicit_instance_names_may_repeat() {
+    init();
+    let name = format!("unit-test-{}", std::process::id());
+    let first = Frontend::builder().instance_name(&name).build().unwrap();
+    let second = Frontend::builder().instance_name(&name).build().unwrap();
+    assert_eq!(first.instance_name(), second.instance_name());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn get_printer_with_bogus_id_is_invalid_printer() {
+    init();
+    let frontend = Frontend::new().expect("Frontend::new failed");
+    let by_id = frontend.get_printer("cpdb-rs-no-such-printer", None);
+    assert!(matches!(by_id, Err(CpdbError::InvalidPrinter)));
+    let by_backend = frontend.get_printer("cpdb-rs-no-such-printer", Some("CUPS"));
+    assert!(matches!(by_backend, Err(CpdbError::InvalidPrinter)));
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn default_printer_is_none_before_discovery() {
+    init();
+    let frontend = Frontend::new().expect("Frontend::new failed");
+    assert!(frontend.default_printer().unwrap().is_none());
+    assert!(
+        frontend
+            .default_printer_for_backend("CUPS")
+            .unwrap()
+            .is_none()
+    );
+}
+
 #[test]
 fn cstr_to_string_handles_valid_input() {
     let cstring = CString::new("hello").unwrap();
@@ -62,6 +193,12 @@ fn cstr_to_string_rejects_null() {
     assert!(matches!(result, Err(CpdbError::NullPointer)));
 }
 
+#[test]
+fn absolute_path_rejects_interior_nul() {
+    let result = cpdb_rs::absolute_path("bad\0path");
+    assert!(matches!(result, Err(CpdbError::InvalidPath(_))));
+}
+
 #[test]
 fn to_c_options_round_trips() {
     let pairs = &[("copies", "2"), ("sides", "two-sided-long-edge")];